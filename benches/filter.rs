@@ -0,0 +1,117 @@
+use std::hint::black_box;
+
+use can::identifier::{ExtendedId, Filter, FilterSet, Id, Mask, StandardId};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+// Number of identifiers matched per iteration.
+const ID_COUNT: usize = 1024;
+
+// Small xorshift generator, so that every run matches the same identifiers.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+
+    fn id(&mut self) -> Id {
+        let raw = self.next();
+        if raw & 1 == 0 {
+            Id::Standard(StandardId::new((raw >> 1) as u16 & 0x7FF).unwrap())
+        } else {
+            Id::Extended(ExtendedId::new(raw >> 1 & 0x1FFF_FFFF).unwrap())
+        }
+    }
+}
+
+fn ids(rng: &mut Rng) -> Vec<Id> {
+    (0..ID_COUNT).map(|_| rng.id()).collect()
+}
+
+// How `Filter::matches` worked before the masked identifier was precomputed: the identifier of the
+// filter is combined with its flags, and masked, on every call.
+fn matches_unmasked(filter: &Filter, id: Id) -> bool {
+    let self_id = filter.id().as_raw() | filter.id().flags().bits();
+    let other_id = id.as_raw() | id.flags().bits();
+    let mask = filter.mask().as_raw();
+
+    other_id & mask == self_id & mask
+}
+
+fn filter_matches(c: &mut Criterion) {
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    let filters = [
+        Filter::standard_identity(StandardId::new(0x7DF).unwrap()),
+        Filter::range(
+            Id::Standard(StandardId::new(0x7E0).unwrap()),
+            Id::Standard(StandardId::new(0x7EF).unwrap()),
+        ),
+        Filter::j1939_pgn(0xF004),
+        Filter::new(rng.id(), Mask::new(rng.next())),
+    ];
+    let ids = ids(&mut rng);
+
+    let mut group = c.benchmark_group("Filter::matches");
+    group.bench_function("unmasked", |b| {
+        b.iter(|| {
+            black_box(&ids)
+                .iter()
+                .flat_map(|id| filters.iter().map(move |f| matches_unmasked(f, *id)))
+                .filter(|matched| *matched)
+                .count()
+        })
+    });
+    group.bench_function("masked", |b| {
+        b.iter(|| {
+            black_box(&ids)
+                .iter()
+                .flat_map(|id| filters.iter().map(move |f| f.matches(*id)))
+                .filter(|matched| *matched)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn filter_set_matches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("FilterSet::matches");
+    for count in [16, 32, 64] {
+        let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+
+        // Filters that each cover a small block of identifiers, as a receive filter for a handful
+        // of messages would, such that most identifiers are compared against every filter.
+        let filters: Vec<Filter> = (0..count)
+            .map(|_| Filter::new(rng.id(), Mask::new(!0xF)))
+            .collect();
+        let set: FilterSet = filters.iter().copied().collect();
+        let ids = ids(&mut rng);
+
+        group.bench_with_input(
+            BenchmarkId::new("iter_any", count),
+            &filters,
+            |b, filters| {
+                b.iter(|| {
+                    black_box(&ids)
+                        .iter()
+                        .filter(|id| filters.iter().any(|f| f.matches(**id)))
+                        .count()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("filter_set", count), &set, |b, set| {
+            b.iter(|| {
+                black_box(&ids)
+                    .iter()
+                    .filter(|id| set.matches(**id))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, filter_matches, filter_set_matches);
+criterion_main!(benches);
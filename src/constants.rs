@@ -4,8 +4,12 @@
 //! frame type in an identifier, or masking specific identifiers in a filter.  However, they're
 //! exposed here in case they are necessary and/or can provide value to users.
 
+use core::fmt;
+
 use bitflags::bitflags;
 
+pub mod j1939;
+
 bitflags! {
     /// Identifier flags for indicating various frame types.
     ///
@@ -19,27 +23,350 @@ bitflags! {
     #[repr(transparent)]
     pub struct IdentifierFlags: u32 {
         /// The frame is using the extended format i.e. 29-bit extended identifiers.
+        ///
+        /// Corresponds to `CAN_EFF_FLAG` in SocketCAN.
         const EXTENDED = 0x80000000;
 
         /// The frame is a remote transmission request.
+        ///
+        /// Corresponds to `CAN_RTR_FLAG` in SocketCAN.
         const REMOTE = 0x40000000;
 
         /// The frame is an error frame.
+        ///
+        /// Corresponds to `CAN_ERR_FLAG` in SocketCAN.
         const ERROR = 0x20000000;
     }
 }
 
+impl IdentifierFlags {
+    /// Gets the addressing flag from this set of flags.
+    ///
+    /// The result is either [`EXTENDED`][Self::EXTENDED], or empty for standard addressing.
+    pub const fn addressing(&self) -> IdentifierFlags {
+        self.intersection(Self::EXTENDED)
+    }
+
+    /// Gets the frame type flags from this set of flags.
+    ///
+    /// The result contains only [`REMOTE`][Self::REMOTE] and [`ERROR`][Self::ERROR], if set, and
+    /// is empty for data frames.
+    pub const fn frame_type(&self) -> IdentifierFlags {
+        self.intersection(Self::REMOTE.union(Self::ERROR))
+    }
+}
+
+impl fmt::Display for IdentifierFlags {
+    /// Formats the set flags by name, separated by `|`, or `(none)` if no flags are set.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("(none)");
+        }
+
+        let names = [
+            (Self::EXTENDED, "EXTENDED"),
+            (Self::REMOTE, "REMOTE"),
+            (Self::ERROR, "ERROR"),
+        ];
+
+        let mut first = true;
+        for (flag, name) in names {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str("|")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for IdentifierFlags {
+    /// Formats the set flags in the same way as [`Display`][fmt::Display].
+    fn format(&self, f: defmt::Formatter<'_>) {
+        if self.is_empty() {
+            defmt::write!(f, "(none)");
+            return;
+        }
+
+        let names = [
+            (Self::EXTENDED, "EXTENDED"),
+            (Self::REMOTE, "REMOTE"),
+            (Self::ERROR, "ERROR"),
+        ];
+
+        let mut first = true;
+        for (flag, name) in names {
+            if self.contains(flag) {
+                if !first {
+                    defmt::write!(f, "|");
+                }
+                defmt::write!(f, "{=str}", name);
+                first = false;
+            }
+        }
+    }
+}
+
+bitflags! {
+    /// CAN FD frame flags.
+    ///
+    /// Unlike [`IdentifierFlags`], these flags are not part of the 32-bit identifier, but are
+    /// carried separately alongside the frame.  The flag values correspond to the `flags` field of
+    /// `canfd_frame` in the Linux [SocketCAN][socketcan] library.
+    ///
+    /// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+    #[repr(transparent)]
+    pub struct FdFlags: u8 {
+        /// The data phase of the frame is transmitted at the data bit rate.
+        ///
+        /// Corresponds to `CANFD_BRS` in SocketCAN.
+        const BRS = 0x01;
+
+        /// The transmitting node is in the error passive state.
+        ///
+        /// Corresponds to `CANFD_ESI` in SocketCAN.
+        const ESI = 0x02;
+
+        /// The frame is a CAN FD frame.
+        ///
+        /// Corresponds to `CANFD_FDF` in SocketCAN.
+        const FDF = 0x04;
+    }
+}
+
+bitflags! {
+    /// Frame status flags.
+    ///
+    /// These flags record problems that occurred while receiving a frame, such as the payload
+    /// being incomplete, or frames having been lost before it.  Like the timestamp and channel of
+    /// a frame, they are capture metadata rather than part of the frame itself.
+    #[repr(transparent)]
+    pub struct FrameStatus: u8 {
+        /// The payload of the frame is incomplete.
+        ///
+        /// This is typically reported when the buffer used to receive the frame was too small,
+        /// such as when a SocketCAN read reports `MSG_TRUNC`.
+        const TRUNCATED = 0x01;
+
+        /// One or more frames were lost before this frame.
+        ///
+        /// This is typically reported when the receive FIFO of a controller overflowed, or when a
+        /// socket dropped frames because its receive queue was full.
+        const FIFO_OVERRUN = 0x02;
+    }
+}
+
+bitflags! {
+    /// Error classes for error frames.
+    ///
+    /// Error frames carry the class of the error, or errors, that occurred in the identifier, with
+    /// further details carried in the data.  The flag values correspond to the format used by the
+    /// Linux [SocketCAN][socketcan] library, as defined in `linux/can/error.h`.
+    ///
+    /// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+    #[repr(transparent)]
+    pub struct ErrorClass: u32 {
+        /// The transmission timed out.
+        const TX_TIMEOUT = 0x00000001;
+
+        /// Arbitration was lost.  The bit position is carried in the first data byte.
+        const LOST_ARBITRATION = 0x00000002;
+
+        /// The controller reported problems.  Details are carried in the second data byte.
+        const CONTROLLER = 0x00000004;
+
+        /// A protocol violation occurred.  Details are carried in the third and fourth data bytes.
+        const PROTOCOL = 0x00000008;
+
+        /// The transceiver reported problems.  Details are carried in the fifth data byte.
+        const TRANSCEIVER = 0x00000010;
+
+        /// No acknowledgement was received on transmission.
+        const NO_ACK = 0x00000020;
+
+        /// The controller entered the bus off state.
+        const BUS_OFF = 0x00000040;
+
+        /// A bus error occurred.
+        const BUS_ERROR = 0x00000080;
+
+        /// The controller restarted.
+        const RESTARTED = 0x00000100;
+
+        /// The transmit and receive error counters are carried in the seventh and eighth data
+        /// bytes.
+        const COUNTERS = 0x00000200;
+    }
+}
+
+bitflags! {
+    /// Controller status for error frames with the [`ErrorClass::CONTROLLER`] class.
+    ///
+    /// The flag values correspond to the format used by the Linux [SocketCAN][socketcan] library,
+    /// as defined in `linux/can/error.h`.
+    ///
+    /// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+    #[repr(transparent)]
+    pub struct ControllerStatus: u8 {
+        /// The receive buffer overflowed.
+        const RX_OVERFLOW = 0x01;
+
+        /// The transmit buffer overflowed.
+        const TX_OVERFLOW = 0x02;
+
+        /// The receive error counter reached the warning level.
+        const RX_WARNING = 0x04;
+
+        /// The transmit error counter reached the warning level.
+        const TX_WARNING = 0x08;
+
+        /// The receive error counter reached the error passive level.
+        const RX_PASSIVE = 0x10;
+
+        /// The transmit error counter reached the error passive level.
+        const TX_PASSIVE = 0x20;
+
+        /// The controller recovered to the error active state.
+        const ACTIVE = 0x40;
+    }
+}
+
+bitflags! {
+    /// Protocol error types for error frames with the [`ErrorClass::PROTOCOL`] class.
+    ///
+    /// The flag values correspond to the format used by the Linux [SocketCAN][socketcan] library,
+    /// as defined in `linux/can/error.h`.
+    ///
+    /// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+    #[repr(transparent)]
+    pub struct ProtocolErrorType: u8 {
+        /// A single bit error.
+        const BIT = 0x01;
+
+        /// A frame format error.
+        const FORM = 0x02;
+
+        /// A bit stuffing error.
+        const STUFF = 0x04;
+
+        /// The controller was unable to send a dominant bit.
+        const BIT0 = 0x08;
+
+        /// The controller was unable to send a recessive bit.
+        const BIT1 = 0x10;
+
+        /// The bus was overloaded.
+        const OVERLOAD = 0x20;
+
+        /// An active error was announced.
+        const ACTIVE = 0x40;
+
+        /// The error occurred on transmission.
+        const TX = 0x80;
+    }
+}
+
 /// Mask for standard identifiers.
 pub const SFF_MASK: u32 = 0x000007ff;
 
 /// Mask for extended identifiers.
 pub const EFF_MASK: u32 = 0x1fffffff;
 
+/// Mask for the error class bits of error frame identifiers.
+///
+/// See [`ErrorClass`] for the error classes themselves.
+pub const ERR_MASK: u32 = 0x1fffffff;
+
+/// Flag for extended identifiers, as a raw value.
+///
+/// Equivalent to [`IdentifierFlags::EXTENDED`], and corresponds to `CAN_EFF_FLAG` in SocketCAN.
+pub const EFF_FLAG: u32 = IdentifierFlags::EXTENDED.bits();
+
+/// Flag for remote transmission requests, as a raw value.
+///
+/// Equivalent to [`IdentifierFlags::REMOTE`], and corresponds to `CAN_RTR_FLAG` in SocketCAN.
+pub const RTR_FLAG: u32 = IdentifierFlags::REMOTE.bits();
+
+/// Flag for error frames, as a raw value.
+///
+/// Equivalent to [`IdentifierFlags::ERROR`], and corresponds to `CAN_ERR_FLAG` in SocketCAN.
+pub const ERR_FLAG: u32 = IdentifierFlags::ERROR.bits();
+
+/// Checks if the extended flag is set in a raw identifier.
+///
+/// The raw identifier is in the format used by SocketCAN, where the identifier flags are encoded in
+/// the upper bits.  See [`IdentifierFlags::EXTENDED`].
+pub const fn is_extended(raw: u32) -> bool {
+    IdentifierFlags::from_bits_truncate(raw).contains(IdentifierFlags::EXTENDED)
+}
+
+/// Checks if the remote flag is set in a raw identifier.
+///
+/// The raw identifier is in the format used by SocketCAN, where the identifier flags are encoded in
+/// the upper bits.  See [`IdentifierFlags::REMOTE`].
+pub const fn is_remote(raw: u32) -> bool {
+    IdentifierFlags::from_bits_truncate(raw).contains(IdentifierFlags::REMOTE)
+}
+
+/// Checks if the error flag is set in a raw identifier.
+///
+/// The raw identifier is in the format used by SocketCAN, where the identifier flags are encoded in
+/// the upper bits.  See [`IdentifierFlags::ERROR`].
+pub const fn is_error(raw: u32) -> bool {
+    IdentifierFlags::from_bits_truncate(raw).contains(IdentifierFlags::ERROR)
+}
+
+// Data lengths for each CAN FD DLC value, where DLCs 0 to 8 map directly to the data length.
+const FD_DLC_LENGTHS: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Converts a CAN FD DLC to the data length it represents.
+///
+/// DLC values 0 to 8 map directly to data lengths of 0 to 8 bytes, while DLC values 9 to 15 map to
+/// data lengths of 12, 16, 20, 24, 32, 48, and 64 bytes, respectively.
+///
+/// Returns `None` if `dlc` is greater than 15.
+pub const fn fd_dlc_to_len(dlc: u8) -> Option<u8> {
+    if (dlc as usize) < FD_DLC_LENGTHS.len() {
+        Some(FD_DLC_LENGTHS[dlc as usize])
+    } else {
+        None
+    }
+}
+
+/// Converts a data length to the smallest CAN FD DLC that can hold it.
+///
+/// Data lengths that are not exactly representable by a DLC, such as 9 bytes, are rounded up to
+/// the next largest DLC, with the data being padded when transmitted.
+///
+/// Returns `None` if `len` is greater than 64.
+pub const fn len_to_fd_dlc(len: u8) -> Option<u8> {
+    let mut dlc = 0;
+    while dlc < FD_DLC_LENGTHS.len() {
+        if FD_DLC_LENGTHS[dlc] >= len {
+            return Some(dlc as u8);
+        }
+        dlc += 1;
+    }
+
+    None
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
+    use std::string::ToString;
+
     use proptest::{arbitrary::any as arb_any, strategy::Strategy};
 
-    use super::IdentifierFlags;
+    use super::{
+        fd_dlc_to_len, is_error, is_extended, is_remote, len_to_fd_dlc, IdentifierFlags, EFF_FLAG,
+        ERR_FLAG, RTR_FLAG,
+    };
 
     pub(crate) fn arb_identifier_flags() -> impl Strategy<Value = IdentifierFlags> {
         arb_any::<(bool, u8)>().prop_map(|(extended, frame_type)| {
@@ -58,4 +385,70 @@ pub(crate) mod tests {
             id_length.union(frame_type)
         })
     }
+
+    #[test]
+    fn fd_dlc_round_trip() {
+        for dlc in 0..=15 {
+            let len = fd_dlc_to_len(dlc).unwrap();
+            assert_eq!(Some(dlc), len_to_fd_dlc(len));
+        }
+
+        assert_eq!(None, fd_dlc_to_len(16));
+    }
+
+    #[test]
+    fn len_to_fd_dlc_rounds_up() {
+        assert_eq!(Some(8), len_to_fd_dlc(8));
+        assert_eq!(Some(9), len_to_fd_dlc(9));
+        assert_eq!(Some(9), len_to_fd_dlc(12));
+        assert_eq!(Some(10), len_to_fd_dlc(13));
+        assert_eq!(Some(15), len_to_fd_dlc(64));
+        assert_eq!(None, len_to_fd_dlc(65));
+    }
+
+    #[test]
+    fn identifier_flags_display() {
+        assert_eq!("(none)", IdentifierFlags::empty().to_string());
+        assert_eq!("REMOTE", IdentifierFlags::REMOTE.to_string());
+        assert_eq!(
+            "EXTENDED|ERROR",
+            (IdentifierFlags::EXTENDED | IdentifierFlags::ERROR).to_string()
+        );
+        assert_eq!("EXTENDED|REMOTE|ERROR", IdentifierFlags::all().to_string());
+    }
+
+    #[test]
+    fn identifier_flags_split() {
+        let flags = IdentifierFlags::EXTENDED | IdentifierFlags::REMOTE | IdentifierFlags::ERROR;
+        assert_eq!(IdentifierFlags::EXTENDED, flags.addressing());
+        assert_eq!(
+            IdentifierFlags::REMOTE | IdentifierFlags::ERROR,
+            flags.frame_type()
+        );
+        assert_eq!(flags, flags.addressing() | flags.frame_type());
+
+        assert!(IdentifierFlags::REMOTE.addressing().is_empty());
+        assert!(IdentifierFlags::EXTENDED.frame_type().is_empty());
+    }
+
+    #[test]
+    fn raw_flag_predicates() {
+        let raw = 0x123;
+        assert!(!is_extended(raw) && !is_remote(raw) && !is_error(raw));
+
+        assert!(is_extended(raw | EFF_FLAG));
+        assert!(!is_remote(raw | EFF_FLAG) && !is_error(raw | EFF_FLAG));
+
+        assert!(is_remote(raw | RTR_FLAG));
+        assert!(!is_extended(raw | RTR_FLAG) && !is_error(raw | RTR_FLAG));
+
+        assert!(is_error(raw | ERR_FLAG));
+        assert!(!is_extended(raw | ERR_FLAG) && !is_remote(raw | ERR_FLAG));
+
+        let all = raw | EFF_FLAG | RTR_FLAG | ERR_FLAG;
+        assert!(is_extended(all) && is_remote(all) && is_error(all));
+
+        // Address bits are never mistaken for flags.
+        assert!(!is_extended(0x1FFFFFFF) && !is_remote(0x1FFFFFFF) && !is_error(0x1FFFFFFF));
+    }
 }
@@ -0,0 +1,212 @@
+//! J1939 identifier layout.
+//!
+//! SAE J1939-21 defines how the 29 bits of an extended identifier are split into fields, from most
+//! significant to least significant:
+//!
+//! | Bits  | Width | Field                           |
+//! |-------|-------|---------------------------------|
+//! | 26-28 | 3     | priority                        |
+//! | 25    | 1     | extended data page (EDP)        |
+//! | 24    | 1     | data page (DP)                  |
+//! | 16-23 | 8     | PDU format (PF)                 |
+//! | 8-15  | 8     | PDU specific (PS)               |
+//! | 0-7   | 8     | source address (SA)             |
+//!
+//! The extended data page, data page, PDU format, and PDU specific fields together form the 18-bit
+//! parameter group number (PGN).  When the PDU format is less than 240 (PDU1), the PDU specific
+//! field holds a destination address rather than being part of the PGN.
+
+const PRIORITY_SHIFT: u32 = 26;
+const PRIORITY_MASK: u32 = 0x7;
+const EXTENDED_DATA_PAGE_SHIFT: u32 = 25;
+const DATA_PAGE_SHIFT: u32 = 24;
+const PDU_FORMAT_SHIFT: u32 = 16;
+const PDU_SPECIFIC_SHIFT: u32 = 8;
+
+/// Mask for the parameter group number, once shifted down past the source address.
+pub const PGN_MASK: u32 = 0x3FFFF;
+
+/// Mask for the PDU specific field, and the source address.
+pub const PDU_SPECIFIC_MASK: u32 = 0xFF;
+
+/// Smallest PDU format value of the PDU2 format.
+///
+/// PDU format values below this are in the PDU1 format, where the PDU specific field is a
+/// destination address.
+pub const PDU2_FORMAT_MIN: u8 = 0xF0;
+
+/// Fields of a J1939 identifier.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Fields {
+    /// Priority, where 0 is the highest priority.  Only the lower three bits are used.
+    pub priority: u8,
+
+    /// Extended data page (EDP) bit.
+    pub extended_data_page: bool,
+
+    /// Data page (DP) bit.
+    pub data_page: bool,
+
+    /// PDU format (PF).
+    pub pdu_format: u8,
+
+    /// PDU specific (PS), which is either the destination address or the group extension.
+    pub pdu_specific: u8,
+
+    /// Source address (SA).
+    pub source: u8,
+}
+
+impl Fields {
+    /// Whether or not the identifier is in the PDU1 format.
+    ///
+    /// In the PDU1 format, the PDU specific field is a destination address.
+    pub const fn is_pdu1(&self) -> bool {
+        self.pdu_format < PDU2_FORMAT_MIN
+    }
+
+    /// Gets the destination address.
+    ///
+    /// # Errors
+    ///
+    /// If the identifier is in the PDU2 format, which has no destination address, `None` is
+    /// returned.
+    pub const fn destination(&self) -> Option<u8> {
+        if self.is_pdu1() {
+            Some(self.pdu_specific)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the parameter group number (PGN).
+    ///
+    /// For the PDU1 format, the PDU specific field is a destination address, and so it is zeroed in
+    /// the PGN.
+    pub const fn pgn(&self) -> u32 {
+        let pdu_specific = if self.is_pdu1() { 0 } else { self.pdu_specific };
+        (pack(
+            0,
+            self.extended_data_page,
+            self.data_page,
+            self.pdu_format,
+            pdu_specific,
+            0,
+        ) >> PDU_SPECIFIC_SHIFT)
+            & PGN_MASK
+    }
+
+    /// Packs these fields into a raw 29-bit identifier.
+    ///
+    /// This is equivalent to [`pack`].
+    pub const fn pack(&self) -> u32 {
+        pack(
+            self.priority,
+            self.extended_data_page,
+            self.data_page,
+            self.pdu_format,
+            self.pdu_specific,
+            self.source,
+        )
+    }
+}
+
+/// Packs J1939 fields into a raw 29-bit identifier.
+///
+/// Only the lower three bits of `priority` are used.
+pub const fn pack(
+    priority: u8,
+    extended_data_page: bool,
+    data_page: bool,
+    pdu_format: u8,
+    pdu_specific: u8,
+    source: u8,
+) -> u32 {
+    (priority as u32 & PRIORITY_MASK) << PRIORITY_SHIFT
+        | (extended_data_page as u32) << EXTENDED_DATA_PAGE_SHIFT
+        | (data_page as u32) << DATA_PAGE_SHIFT
+        | (pdu_format as u32) << PDU_FORMAT_SHIFT
+        | (pdu_specific as u32) << PDU_SPECIFIC_SHIFT
+        | source as u32
+}
+
+/// Unpacks a raw 29-bit identifier into J1939 fields.
+///
+/// Any bits above the lower 29 bits, such as identifier flags, are ignored.
+pub const fn unpack(raw: u32) -> Fields {
+    Fields {
+        priority: ((raw >> PRIORITY_SHIFT) & PRIORITY_MASK) as u8,
+        extended_data_page: (raw >> EXTENDED_DATA_PAGE_SHIFT) & 1 == 1,
+        data_page: (raw >> DATA_PAGE_SHIFT) & 1 == 1,
+        pdu_format: (raw >> PDU_FORMAT_SHIFT) as u8,
+        pdu_specific: (raw >> PDU_SPECIFIC_SHIFT) as u8,
+        source: raw as u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{arbitrary::any as arb_any, proptest};
+
+    use super::{pack, unpack, Fields};
+    use crate::constants::{EFF_FLAG, EFF_MASK};
+
+    #[test]
+    fn known_identifiers() {
+        // PDU1: TP.CM (PGN 0xEC00) to destination 0x00, from source 0xF9, priority 7, with both
+        // data page bits set.
+        let raw = pack(7, true, true, 0xEC, 0x00, 0xF9);
+        assert_eq!(0x1FEC00F9, raw);
+
+        let fields = unpack(raw);
+        assert_eq!(
+            Fields {
+                priority: 7,
+                extended_data_page: true,
+                data_page: true,
+                pdu_format: 0xEC,
+                pdu_specific: 0x00,
+                source: 0xF9,
+            },
+            fields
+        );
+        assert!(fields.is_pdu1());
+        assert_eq!(Some(0x00), fields.destination());
+        assert_eq!(0x3EC00, fields.pgn());
+        assert_eq!(raw, fields.pack());
+
+        // PDU1 destination addresses are not part of the PGN.
+        let fields = unpack(0x18DAF110);
+        assert_eq!(6, fields.priority);
+        assert_eq!(Some(0xF1), fields.destination());
+        assert_eq!(0x0DA00, fields.pgn());
+
+        // PDU2: EEC1 (PGN 0xF004) from source 0x00, priority 3.
+        let fields = unpack(0x0CF00400);
+        assert_eq!(3, fields.priority);
+        assert!(!fields.extended_data_page);
+        assert!(!fields.data_page);
+        assert!(!fields.is_pdu1());
+        assert_eq!(None, fields.destination());
+        assert_eq!(0xF004, fields.pgn());
+        assert_eq!(0x00, fields.source);
+
+        // Flags are ignored.
+        assert_eq!(fields, unpack(0x0CF00400 | EFF_FLAG));
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(raw in 0..=EFF_MASK) {
+            assert!(unpack(raw).pack() == raw);
+        }
+
+        #[test]
+        fn pack_masks_priority(fields in arb_any::<(u8, bool, bool, u8, u8, u8)>()) {
+            let (priority, edp, dp, pf, ps, sa) = fields;
+            let raw = pack(priority, edp, dp, pf, ps, sa);
+            assert!(raw <= EFF_MASK);
+            assert!(unpack(raw).priority == priority & 0x7);
+        }
+    }
+}
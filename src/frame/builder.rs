@@ -0,0 +1,149 @@
+use bytes::{BufMut, BytesMut};
+
+use crate::identifier::Id;
+
+use super::{Frame, FrameLengthError, CLASSIC_MAX_DATA_LEN};
+
+/// Incremental builder for a [`Frame`].
+///
+/// Payloads that are assembled field by field can be pushed into the builder one value at a time,
+/// with the length of the payload only being checked once the frame is built.  By default, the
+/// builder produces a classic CAN frame, limited to eight bytes of data.  Calling
+/// [`fd`][Self::fd] switches to producing a CAN FD frame, limited to 64 bytes of data.
+///
+/// A builder is created with [`Frame::builder`].
+#[derive(Clone, Debug)]
+pub struct FrameBuilder {
+    id: Id,
+    data: BytesMut,
+    kind: FrameKind,
+}
+
+// Type of frame produced by a `FrameBuilder`.
+#[derive(Clone, Copy, Debug)]
+enum FrameKind {
+    Classic,
+    Fd { brs: bool },
+}
+
+impl FrameBuilder {
+    /// Updates this builder to produce a CAN FD frame.
+    ///
+    /// If `brs` is `true`, the bit rate switch flag is set on the frame.
+    pub fn fd(mut self, brs: bool) -> Self {
+        self.kind = FrameKind::Fd { brs };
+        self
+    }
+
+    /// Appends a single byte to the payload.
+    pub fn push_u8(mut self, value: u8) -> Self {
+        self.data.put_u8(value);
+        self
+    }
+
+    /// Appends a 16-bit value to the payload, in big-endian byte order.
+    pub fn push_u16_be(mut self, value: u16) -> Self {
+        self.data.put_u16(value);
+        self
+    }
+
+    /// Appends a slice of bytes to the payload.
+    pub fn extend_from_slice(mut self, data: &[u8]) -> Self {
+        self.data.extend_from_slice(data);
+        self
+    }
+
+    /// Gets the number of bytes in the payload so far.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if no bytes have been appended to the payload.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Builds the frame.
+    ///
+    /// CAN FD payloads that are not one of the valid CAN FD data lengths are padded with zeros up
+    /// to the next valid length, as described in [`Frame::new_fd`].
+    ///
+    /// # Errors
+    ///
+    /// If the payload is larger than eight bytes for a classic CAN frame, or larger than 64 bytes
+    /// for a CAN FD frame, an error is returned.
+    pub fn build(self) -> Result<Frame, FrameLengthError> {
+        match self.kind {
+            FrameKind::Classic => Frame::try_new_classic(self.id, self.data.freeze()),
+            FrameKind::Fd { brs } => Frame::new_fd(self.id, self.data.freeze(), brs),
+        }
+    }
+}
+
+impl Frame {
+    /// Creates a [`FrameBuilder`] for a frame with the given identifier.
+    ///
+    /// The builder initially produces a classic CAN frame with no data.
+    pub fn builder(id: Id) -> FrameBuilder {
+        FrameBuilder {
+            id,
+            data: BytesMut::with_capacity(CLASSIC_MAX_DATA_LEN),
+            kind: FrameKind::Classic,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::identifier::{Id, StandardId};
+
+    use super::Frame;
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    #[test]
+    fn field_by_field() {
+        let frame = Frame::builder(sid(0x123))
+            .push_u8(0x01)
+            .push_u16_be(0x0203)
+            .extend_from_slice(&[0x04, 0x05])
+            .build()
+            .unwrap();
+        assert_eq!(
+            Frame::from_static(sid(0x123), &[0x01, 0x02, 0x03, 0x04, 0x05]),
+            frame
+        );
+        assert!(!frame.is_fd());
+
+        let empty = Frame::builder(sid(0x123)).build().unwrap();
+        assert_eq!(Frame::from_static(sid(0x123), &[]), empty);
+    }
+
+    #[test]
+    fn length_limits() {
+        let classic = Frame::builder(sid(0x123)).extend_from_slice(&[0xAA; 9]);
+        assert_eq!(9, classic.len());
+
+        let err = classic.clone().build().unwrap_err();
+        assert_eq!(9, err.data_len());
+        assert_eq!(8, err.max_len());
+
+        let fd = classic.fd(true).build().unwrap();
+        assert!(fd.is_fd());
+        assert!(fd.bit_rate_switch());
+        assert!(fd.is_valid_fd());
+        assert_eq!(12, fd.len());
+        assert_eq!(&[0xAA; 9][..], &fd.data()[..9]);
+        assert_eq!(&[0x00; 3][..], &fd.data()[9..]);
+
+        let err = Frame::builder(sid(0x123))
+            .fd(false)
+            .extend_from_slice(&[0; 65])
+            .build()
+            .unwrap_err();
+        assert_eq!(65, err.data_len());
+        assert_eq!(64, err.max_len());
+    }
+}
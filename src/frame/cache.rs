@@ -0,0 +1,177 @@
+use std::{
+    collections::{hash_map, HashMap},
+    time::Duration,
+};
+
+use crate::identifier::Id;
+
+use super::Frame;
+
+#[derive(Clone, Debug)]
+struct Entry {
+    frame: Frame,
+    updates: u64,
+}
+
+/// Cache of the most recent frame for each identifier.
+///
+/// Frames are keyed by their identifier, including its flags, such that a remote frame does not
+/// replace the most recent data frame for the same identifier.  Alongside the most recent frame,
+/// the number of times each identifier has been updated is tracked.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone, Debug, Default)]
+pub struct LatestFrameCache {
+    entries: HashMap<Id, Entry>,
+}
+
+impl LatestFrameCache {
+    /// Creates an empty [`LatestFrameCache`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the cache with the given frame.
+    ///
+    /// If a frame with the same identifier is already cached, it is replaced.
+    pub fn update(&mut self, frame: Frame) {
+        match self.entries.entry(frame.id()) {
+            hash_map::Entry::Occupied(mut entry) => {
+                let entry = entry.get_mut();
+                entry.frame = frame;
+                entry.updates += 1;
+            }
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(Entry { frame, updates: 1 });
+            }
+        }
+    }
+
+    /// Gets the most recent frame for the given identifier.
+    ///
+    /// # Errors
+    ///
+    /// If no frame has been cached for the identifier, `None` is returned.
+    pub fn get(&self, id: Id) -> Option<&Frame> {
+        self.entries.get(&id).map(|entry| &entry.frame)
+    }
+
+    /// Gets the number of times the given identifier has been updated.
+    ///
+    /// If no frame has been cached for the identifier, zero is returned.
+    pub fn update_count(&self, id: Id) -> u64 {
+        self.entries.get(&id).map_or(0, |entry| entry.updates)
+    }
+
+    /// Gets the timestamp of the most recent frame for the given identifier.
+    ///
+    /// # Errors
+    ///
+    /// If no frame has been cached for the identifier, or the most recent frame has no timestamp,
+    /// `None` is returned.
+    pub fn last_seen(&self, id: Id) -> Option<Duration> {
+        self.get(id).and_then(Frame::timestamp)
+    }
+
+    /// Removes the most recent frame for the given identifier, returning it.
+    ///
+    /// The update count for the identifier is reset.
+    pub fn remove(&mut self, id: Id) -> Option<Frame> {
+        self.entries.remove(&id).map(|entry| entry.frame)
+    }
+
+    /// Gets the number of identifiers in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether or not the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all frames from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Gets an iterator over the most recent frame for each identifier, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &Frame> {
+        self.entries.values().map(|entry| &entry.frame)
+    }
+}
+
+impl Extend<Frame> for LatestFrameCache {
+    fn extend<T: IntoIterator<Item = Frame>>(&mut self, iter: T) {
+        for frame in iter {
+            self.update(frame);
+        }
+    }
+}
+
+impl FromIterator<Frame> for LatestFrameCache {
+    fn from_iter<T: IntoIterator<Item = Frame>>(iter: T) -> Self {
+        let mut cache = Self::new();
+        cache.extend(iter);
+        cache
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::identifier::{Id, StandardId};
+
+    use super::{Frame, LatestFrameCache};
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    #[test]
+    fn later_frame_overwrites() {
+        let mut cache = LatestFrameCache::new();
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.get(sid(0x123)));
+        assert_eq!(0, cache.update_count(sid(0x123)));
+
+        cache
+            .update(Frame::from_static(sid(0x123), &[0x01]).with_timestamp(Duration::from_secs(1)));
+        cache.update(Frame::from_static(sid(0x456), &[0x02]));
+        cache
+            .update(Frame::from_static(sid(0x123), &[0x03]).with_timestamp(Duration::from_secs(2)));
+
+        assert_eq!(2, cache.len());
+        assert_eq!(&[0x03][..], cache.get(sid(0x123)).unwrap().data());
+        assert_eq!(&[0x02][..], cache.get(sid(0x456)).unwrap().data());
+        assert_eq!(2, cache.update_count(sid(0x123)));
+        assert_eq!(1, cache.update_count(sid(0x456)));
+        assert_eq!(Some(Duration::from_secs(2)), cache.last_seen(sid(0x123)));
+        assert_eq!(None, cache.last_seen(sid(0x456)));
+
+        // Remote frames are cached separately from data frames.
+        cache.update(Frame::remote(sid(0x123), 1).unwrap());
+        assert_eq!(3, cache.len());
+        assert_eq!(&[0x03][..], cache.get(sid(0x123)).unwrap().data());
+
+        assert!(cache.remove(sid(0x456)).is_some());
+        assert_eq!(0, cache.update_count(sid(0x456)));
+        assert_eq!(2, cache.iter().count());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn from_iter() {
+        let cache = (0..10u8)
+            .map(|i| Frame::from_slice(sid(0x100 + u16::from(i % 3)), &[i]))
+            .collect::<LatestFrameCache>();
+
+        assert_eq!(3, cache.len());
+        assert_eq!(&[9][..], cache.get(sid(0x100)).unwrap().data());
+        assert_eq!(&[7][..], cache.get(sid(0x101)).unwrap().data());
+        assert_eq!(&[8][..], cache.get(sid(0x102)).unwrap().data());
+        assert_eq!(4, cache.update_count(sid(0x100)));
+    }
+}
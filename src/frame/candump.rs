@@ -0,0 +1,405 @@
+//! candump log support.
+//!
+//! The `candump` utility from [can-utils][can_utils] prints frames in a compact text format, which
+//! is also used by `cansend`, and for log files written by `candump -l` or `candump -L`.  Each
+//! frame is written as the identifier, a `#` separator, and then the data, all encoded as
+//! hexadecimal:
+//!
+//! - standard data frame, e.g. `7E8#02010C`
+//! - extended data frame, e.g. `18DAF110#02010C`
+//! - remote frame, optionally followed by the requested DLC, e.g. `7DF#R` or `7DF#R4`
+//! - CAN FD frame, with a second `#` followed by the CAN FD flags, e.g. `7E8##10102030405060708`
+//! - error frame, with the error flag set in the identifier, e.g. `20000004#0004000000000000`
+//!
+//! Standard identifiers are always written as three digits, while extended identifiers and error
+//! frame identifiers are always written as eight digits.
+//!
+//! Log lines additionally start with the timestamp, in seconds, and the interface name, e.g.
+//! `(1609459200.000000) can0 7E8#02010C`.
+//!
+//! [can_utils]: https://github.com/linux-can/can-utils
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write as _},
+    time::Duration,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    constants::{FdFlags, IdentifierFlags, EFF_MASK, ERR_FLAG, ERR_MASK},
+    identifier::{ExtendedId, Id, StandardId},
+};
+
+use super::{hex_value, Channel, Frame};
+
+// Number of hexadecimal digits used to encode a standard and extended identifier, respectively.
+const STANDARD_ID_DIGITS: usize = 3;
+const EXTENDED_ID_DIGITS: usize = 8;
+
+// Maximum number of fractional digits in a timestamp, which is enough for nanosecond precision.
+const TIMESTAMP_MAX_FRACTION_DIGITS: usize = 9;
+
+/// An error that occurred while parsing a candump frame or log line.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CandumpError {
+    /// The line was not in the expected format.
+    Malformed,
+
+    /// The line contained a character that was not a valid hexadecimal digit.
+    InvalidHex,
+
+    /// The identifier was out of range for its addressing mode.
+    InvalidIdentifier,
+
+    /// The frame carried more data, or requested more data, than is allowed for its frame type.
+    TooMuchData,
+
+    /// The timestamp was not in the expected format.
+    InvalidTimestamp,
+}
+
+impl fmt::Display for CandumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "malformed candump line"),
+            Self::InvalidHex => write!(f, "invalid hexadecimal digit in candump line"),
+            Self::InvalidIdentifier => write!(f, "identifier out of range in candump line"),
+            Self::TooMuchData => write!(f, "too much data for frame type in candump line"),
+            Self::InvalidTimestamp => write!(f, "invalid timestamp in candump line"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for CandumpError {}
+
+impl Frame {
+    /// Parses a frame in the compact candump format, such as `7E8#02010C`.
+    ///
+    /// Data bytes may optionally be separated by `.`, as accepted by `cansend`.
+    ///
+    /// # Errors
+    ///
+    /// If the frame is not valid, an error describing the problem is returned.
+    pub fn from_candump_line(line: &str) -> Result<Frame, CandumpError> {
+        let (raw_id, body) = line.trim().split_once('#').ok_or(CandumpError::Malformed)?;
+        let id = parse_id(raw_id)?;
+
+        if let Some(body) = body.strip_prefix('#') {
+            let flags = body.bytes().next().ok_or(CandumpError::Malformed)?;
+            let flags = FdFlags::from_bits_truncate(hex_digit(flags)?);
+            let data = parse_data(&body[1..])?;
+
+            return Frame::new_fd(id, Bytes::from(data), flags.contains(FdFlags::BRS))
+                .map_err(|_| CandumpError::TooMuchData)
+                .map(|frame| {
+                    frame
+                        .with_error_state_indicator(flags.contains(FdFlags::ESI))
+                        .expect("CAN FD frames should accept the error state indicator")
+                });
+        }
+
+        if let Some(dlc) = body.strip_prefix('R').or_else(|| body.strip_prefix('r')) {
+            let dlc = match dlc.as_bytes() {
+                [] => 0,
+                [dlc] => hex_digit(*dlc)?,
+                _ => return Err(CandumpError::Malformed),
+            };
+
+            return Frame::remote(id, dlc).ok_or(CandumpError::TooMuchData);
+        }
+
+        let data = parse_data(body)?;
+        Frame::try_from_slice(id, &data).map_err(|_| CandumpError::TooMuchData)
+    }
+
+    /// Formats this frame in the compact candump format, such as `7E8#02010C`.
+    ///
+    /// All of the data is written, even if there is more than fits in a single frame.
+    pub fn to_candump_line(&self) -> String {
+        let mut line = String::with_capacity(EXTENDED_ID_DIGITS + 3 + self.len() * 2);
+
+        // Writing to a `String` never fails.
+        let id = self.id();
+        let _ = if self.is_error_frame() {
+            write!(line, "{:08X}#", id.as_raw() | ERR_FLAG)
+        } else {
+            match id {
+                Id::Standard(id) => write!(line, "{:03X}#", id.as_raw()),
+                Id::Extended(id) => write!(line, "{:08X}#", id.as_raw()),
+            }
+        };
+
+        if self.is_remote_frame() {
+            line.push('R');
+            if self.dlc() > 0 {
+                let _ = write!(line, "{:X}", self.dlc());
+            }
+
+            return line;
+        }
+
+        if self.is_fd() {
+            let flags = self.fd_flags().difference(FdFlags::FDF);
+            let _ = write!(line, "#{:X}", flags.bits());
+        }
+
+        for byte in self.data() {
+            let _ = write!(line, "{:02X}", byte);
+        }
+
+        line
+    }
+
+    /// Parses a candump log line, such as `(1609459200.000000) can0 7E8#02010C`.
+    ///
+    /// The timestamp of the frame is set from the log line.  If the interface name ends with a
+    /// number, such as `can0`, the channel of the frame is also set from that number.  The interface
+    /// name is returned alongside the frame.
+    ///
+    /// Anything following the frame, such as the direction written by `candump -x`, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the log line is not valid, an error describing the problem is returned.
+    pub fn from_candump_log_line(line: &str) -> Result<(Frame, &str), CandumpError> {
+        let mut parts = line.split_whitespace();
+        let (timestamp, interface, frame) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(timestamp), Some(interface), Some(frame)) => (timestamp, interface, frame),
+            _ => return Err(CandumpError::Malformed),
+        };
+
+        let timestamp = parse_timestamp(timestamp)?;
+        let mut frame = Frame::from_candump_line(frame)?.with_timestamp(timestamp);
+
+        let number_start = interface
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .len();
+        if let Ok(index) = interface[number_start..].parse::<u8>() {
+            frame = frame.with_channel(Channel::new(index));
+        }
+
+        Ok((frame, interface))
+    }
+
+    /// Formats this frame as a candump log line, such as `(1609459200.000000) can0 7E8#02010C`.
+    ///
+    /// If this frame has no timestamp, a timestamp of zero is written.
+    pub fn to_candump_log_line(&self, interface: &str) -> String {
+        let timestamp = self.timestamp().unwrap_or_default();
+        let mut line = String::new();
+
+        // Writing to a `String` never fails.
+        let _ = write!(
+            line,
+            "({}.{:06}) {} {}",
+            timestamp.as_secs(),
+            timestamp.subsec_micros(),
+            interface,
+            self.to_candump_line()
+        );
+
+        line
+    }
+}
+
+fn hex_digit(c: u8) -> Result<u8, CandumpError> {
+    hex_value(c).ok_or(CandumpError::InvalidHex)
+}
+
+fn parse_id(raw: &str) -> Result<Id, CandumpError> {
+    let value = raw
+        .bytes()
+        .try_fold(0u32, |acc, c| Ok(acc << 4 | u32::from(hex_digit(c)?)))?;
+
+    match raw.len() {
+        STANDARD_ID_DIGITS => u16::try_from(value)
+            .ok()
+            .and_then(StandardId::new)
+            .map(Id::Standard)
+            .ok_or(CandumpError::InvalidIdentifier),
+        // Error frames are written with the error flag set, and use a standard identifier for the
+        // error class, matching `Frame::error`.
+        EXTENDED_ID_DIGITS if value & ERR_FLAG != 0 => u16::try_from(value & ERR_MASK)
+            .ok()
+            .and_then(|class| StandardId::with_flags(class, IdentifierFlags::ERROR))
+            .map(Id::Standard)
+            .ok_or(CandumpError::InvalidIdentifier),
+        EXTENDED_ID_DIGITS if value <= EFF_MASK => Ok(Id::Extended(
+            ExtendedId::new(value).ok_or(CandumpError::InvalidIdentifier)?,
+        )),
+        EXTENDED_ID_DIGITS => Err(CandumpError::InvalidIdentifier),
+        _ => Err(CandumpError::Malformed),
+    }
+}
+
+fn parse_data(raw: &str) -> Result<Vec<u8>, CandumpError> {
+    let mut digits = raw.bytes().filter(|&c| c != b'.');
+    let mut data = Vec::with_capacity(raw.len() / 2);
+    while let Some(high) = digits.next() {
+        let low = digits.next().ok_or(CandumpError::Malformed)?;
+        data.push(hex_digit(high)? << 4 | hex_digit(low)?);
+    }
+
+    Ok(data)
+}
+
+fn parse_timestamp(raw: &str) -> Result<Duration, CandumpError> {
+    let (secs, fraction) = raw
+        .strip_prefix('(')
+        .and_then(|raw| raw.strip_suffix(')'))
+        .and_then(|raw| raw.split_once('.'))
+        .ok_or(CandumpError::InvalidTimestamp)?;
+
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+    if !all_digits(secs) || !all_digits(fraction) || fraction.len() > TIMESTAMP_MAX_FRACTION_DIGITS
+    {
+        return Err(CandumpError::InvalidTimestamp);
+    }
+
+    let secs = secs
+        .parse::<u64>()
+        .map_err(|_| CandumpError::InvalidTimestamp)?;
+    let nanos = fraction
+        .parse::<u32>()
+        .map_err(|_| CandumpError::InvalidTimestamp)?
+        * 10u32.pow((TIMESTAMP_MAX_FRACTION_DIGITS - fraction.len()) as u32);
+
+    Ok(Duration::new(secs, nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        constants::ErrorClass,
+        frame::Channel,
+        identifier::{ExtendedId, Id, StandardId},
+    };
+
+    use super::{CandumpError, Frame};
+
+    const LOG_LINES: &[&str] = &[
+        "(1436509052.249713) vcan0 44C#3B1700000000000C",
+        "(1609459200.000000) can0 7E8#02010C",
+        "(1609459200.123456) can1 18DAF110#0322F190",
+        "(1609459201.000001) can0 7DF#R",
+        "(1609459201.500000) can0 7DF#R4",
+        "(1609459202.000000) can0 7E8##10102030405060708090A0B0C",
+        "(1609459202.000000) can0 18DAF110##3",
+        "(1609459203.000000) can0 20000004#0004000000000000",
+    ];
+
+    #[test]
+    fn round_trip() {
+        for line in LOG_LINES {
+            let (frame, interface) = Frame::from_candump_log_line(line).unwrap();
+            assert_eq!(*line, frame.to_candump_log_line(interface));
+
+            let (_, compact) = line.rsplit_once(' ').unwrap();
+            let timestamp = frame.timestamp().unwrap();
+            let parsed = Frame::from_candump_line(compact).unwrap();
+            assert_eq!(frame, parsed.with_timestamp(timestamp));
+            assert_eq!(compact, frame.to_candump_line());
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let sid = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let eid = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+
+        let (frame, interface) =
+            Frame::from_candump_log_line("(1609459200.123456) can1 18DAF110#0322F190").unwrap();
+        assert_eq!("can1", interface);
+        assert_eq!(eid, frame.id());
+        assert_eq!(&[0x03, 0x22, 0xF1, 0x90], frame.data());
+        assert_eq!(
+            Some(Duration::new(1609459200, 123456000)),
+            frame.timestamp()
+        );
+        assert_eq!(Some(Channel::new(1)), frame.channel());
+
+        let (frame, _) = Frame::from_candump_log_line("(0.5) vcan 7E8#").unwrap();
+        assert_eq!(Some(Duration::from_millis(500)), frame.timestamp());
+        assert_eq!(None, frame.channel());
+
+        assert_eq!(
+            Ok(Frame::from_slice(sid, &[0x02, 0x01, 0x0C])),
+            Frame::from_candump_line("7E8#02.01.0c")
+        );
+
+        let frame = Frame::from_candump_line("7E8##3AA").unwrap();
+        assert!(frame.is_fd());
+        assert!(frame.bit_rate_switch());
+        assert!(frame.error_state_indicator());
+        assert_eq!(&[0xAA], frame.data());
+
+        let frame = Frame::from_candump_line("7DF#R4").unwrap();
+        assert!(frame.is_remote_frame());
+        assert_eq!(4, frame.dlc());
+
+        let frame = Frame::from_candump_line("20000004#0004000000000000").unwrap();
+        assert_eq!(Some(ErrorClass::CONTROLLER), frame.error_class());
+        assert_eq!(
+            Frame::error(ErrorClass::CONTROLLER, [0, 4, 0, 0, 0, 0, 0, 0]),
+            frame
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Err(CandumpError::Malformed),
+            Frame::from_candump_line("7E8")
+        );
+        assert_eq!(
+            Err(CandumpError::Malformed),
+            Frame::from_candump_line("7E#00")
+        );
+        assert_eq!(
+            Err(CandumpError::Malformed),
+            Frame::from_candump_line("7E8#0")
+        );
+        assert_eq!(
+            Err(CandumpError::Malformed),
+            Frame::from_candump_line("7E8#R12")
+        );
+        assert_eq!(
+            Err(CandumpError::InvalidHex),
+            Frame::from_candump_line("7E8#0G")
+        );
+        assert_eq!(
+            Err(CandumpError::InvalidIdentifier),
+            Frame::from_candump_line("800#00")
+        );
+        assert_eq!(
+            Err(CandumpError::InvalidIdentifier),
+            Frame::from_candump_line("40000000#00")
+        );
+        assert_eq!(
+            Err(CandumpError::TooMuchData),
+            Frame::from_candump_line("7E8#000102030405060708")
+        );
+        assert_eq!(
+            Err(CandumpError::TooMuchData),
+            Frame::from_candump_line("7DF#R9")
+        );
+        assert_eq!(
+            Err(CandumpError::Malformed),
+            Frame::from_candump_log_line("(0.0) can0")
+        );
+        assert_eq!(
+            Err(CandumpError::InvalidTimestamp),
+            Frame::from_candump_log_line("0.0 can0 7E8#00")
+        );
+        assert_eq!(
+            Err(CandumpError::InvalidTimestamp),
+            Frame::from_candump_log_line("(1.-5) can0 7E8#00")
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::constants::{ControllerStatus, ErrorClass, ProtocolErrorType};
+
+use super::Frame;
+
+// Offsets of the detail bytes in the data of an error frame.
+const LOST_ARBITRATION_OFFSET: usize = 0;
+const CONTROLLER_OFFSET: usize = 1;
+const PROTOCOL_TYPE_OFFSET: usize = 2;
+const PROTOCOL_LOCATION_OFFSET: usize = 3;
+const TRANSCEIVER_OFFSET: usize = 4;
+const TX_ERROR_COUNTER_OFFSET: usize = 6;
+const RX_ERROR_COUNTER_OFFSET: usize = 7;
+
+/// Location within a frame where a protocol error occurred.
+///
+/// The values correspond to the format used by the Linux [SocketCAN][socketcan] library, as defined
+/// in `linux/can/error.h`.
+///
+/// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ProtocolErrorLocation {
+    /// The location is unspecified.
+    Unspecified,
+
+    /// Start of frame.
+    StartOfFrame,
+
+    /// Identifier bits 28 to 21, or bits 10 to 3 for standard identifiers.
+    Id28To21,
+
+    /// Identifier bits 20 to 18, or bits 2 to 0 for standard identifiers.
+    Id20To18,
+
+    /// Substitute remote request bit.
+    SubstituteRemoteRequest,
+
+    /// Identifier extension bit.
+    IdentifierExtension,
+
+    /// Identifier bits 17 to 13.
+    Id17To13,
+
+    /// Identifier bits 12 to 5.
+    Id12To5,
+
+    /// Identifier bits 4 to 0.
+    Id4To0,
+
+    /// Remote transmission request bit.
+    RemoteTransmissionRequest,
+
+    /// Reserved bit 1.
+    Reserved1,
+
+    /// Reserved bit 0.
+    Reserved0,
+
+    /// Data length code.
+    DataLengthCode,
+
+    /// Data field.
+    Data,
+
+    /// CRC sequence.
+    CrcSequence,
+
+    /// CRC delimiter.
+    CrcDelimiter,
+
+    /// ACK slot.
+    AckSlot,
+
+    /// ACK delimiter.
+    AckDelimiter,
+
+    /// End of frame.
+    EndOfFrame,
+
+    /// Intermission.
+    Intermission,
+
+    /// A location value that is not known.
+    Unknown(u8),
+}
+
+impl ProtocolErrorLocation {
+    const fn from_raw(raw: u8) -> Self {
+        match raw {
+            0x00 => Self::Unspecified,
+            0x03 => Self::StartOfFrame,
+            0x02 => Self::Id28To21,
+            0x06 => Self::Id20To18,
+            0x04 => Self::SubstituteRemoteRequest,
+            0x05 => Self::IdentifierExtension,
+            0x07 => Self::Id17To13,
+            0x0F => Self::Id12To5,
+            0x0E => Self::Id4To0,
+            0x0C => Self::RemoteTransmissionRequest,
+            0x0D => Self::Reserved1,
+            0x09 => Self::Reserved0,
+            0x0B => Self::DataLengthCode,
+            0x0A => Self::Data,
+            0x08 => Self::CrcSequence,
+            0x18 => Self::CrcDelimiter,
+            0x19 => Self::AckSlot,
+            0x1B => Self::AckDelimiter,
+            0x1A => Self::EndOfFrame,
+            0x12 => Self::Intermission,
+            raw => Self::Unknown(raw),
+        }
+    }
+}
+
+/// A decoded error frame.
+///
+/// Error frames carry the class of the error, or errors, that occurred in the identifier, with
+/// further details carried in the data, following the layout used by the Linux
+/// [SocketCAN][socketcan] library.  Details are only available when the corresponding error class
+/// is present.
+///
+/// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CanError {
+    class: ErrorClass,
+    details: [u8; 8],
+}
+
+impl CanError {
+    /// Gets the error class.
+    pub const fn class(&self) -> ErrorClass {
+        self.class
+    }
+
+    /// Gets the bit position at which arbitration was lost.
+    ///
+    /// A value of zero indicates that the bit position is unspecified.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::LOST_ARBITRATION`], `None` is returned.
+    pub const fn arbitration_lost_bit(&self) -> Option<u8> {
+        if self.class.contains(ErrorClass::LOST_ARBITRATION) {
+            Some(self.details[LOST_ARBITRATION_OFFSET])
+        } else {
+            None
+        }
+    }
+
+    /// Gets the controller status.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::CONTROLLER`], `None` is returned.
+    pub const fn controller_status(&self) -> Option<ControllerStatus> {
+        if self.class.contains(ErrorClass::CONTROLLER) {
+            Some(ControllerStatus::from_bits_truncate(
+                self.details[CONTROLLER_OFFSET],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the type of the protocol error.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::PROTOCOL`], `None` is returned.
+    pub const fn protocol_error_type(&self) -> Option<ProtocolErrorType> {
+        if self.class.contains(ErrorClass::PROTOCOL) {
+            Some(ProtocolErrorType::from_bits_truncate(
+                self.details[PROTOCOL_TYPE_OFFSET],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the location of the protocol error.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::PROTOCOL`], `None` is returned.
+    pub const fn protocol_error_location(&self) -> Option<ProtocolErrorLocation> {
+        if self.class.contains(ErrorClass::PROTOCOL) {
+            Some(ProtocolErrorLocation::from_raw(
+                self.details[PROTOCOL_LOCATION_OFFSET],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the raw transceiver status.
+    ///
+    /// The upper four bits describe the status of the CAN-H line, and the lower four bits describe
+    /// the status of the CAN-L line, as defined in `linux/can/error.h`.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::TRANSCEIVER`], `None` is returned.
+    pub const fn transceiver_status(&self) -> Option<u8> {
+        if self.class.contains(ErrorClass::TRANSCEIVER) {
+            Some(self.details[TRANSCEIVER_OFFSET])
+        } else {
+            None
+        }
+    }
+
+    /// Gets the transmit and receive error counters, in that order.
+    ///
+    /// # Errors
+    ///
+    /// If the error class does not include [`ErrorClass::COUNTERS`], `None` is returned.
+    pub const fn error_counters(&self) -> Option<(u8, u8)> {
+        if self.class.contains(ErrorClass::COUNTERS) {
+            Some((
+                self.details[TX_ERROR_COUNTER_OFFSET],
+                self.details[RX_ERROR_COUNTER_OFFSET],
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl Frame {
+    /// Decodes this frame as an error frame.
+    ///
+    /// If the data is shorter than eight bytes, the missing detail bytes are treated as zero.
+    ///
+    /// # Errors
+    ///
+    /// If this is not an error frame, `None` is returned.
+    pub fn decode_error(&self) -> Option<CanError> {
+        let class = self.error_class()?;
+
+        let mut details = [0; 8];
+        let len = self.data().len().min(details.len());
+        details[..len].copy_from_slice(&self.data()[..len]);
+
+        Some(CanError { class, details })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constants::{ControllerStatus, ErrorClass, ProtocolErrorType},
+        frame::Frame,
+        identifier::{Id, StandardId},
+    };
+
+    use super::ProtocolErrorLocation;
+
+    #[test]
+    fn bus_off() {
+        let class = ErrorClass::BUS_OFF | ErrorClass::CONTROLLER | ErrorClass::COUNTERS;
+        let frame = Frame::error(class, [0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x80]);
+        let error = frame.decode_error().unwrap();
+
+        assert_eq!(class, error.class());
+        assert_eq!(
+            Some(ControllerStatus::TX_PASSIVE),
+            error.controller_status()
+        );
+        assert_eq!(Some((0xFF, 0x80)), error.error_counters());
+        assert_eq!(None, error.arbitration_lost_bit());
+        assert_eq!(None, error.protocol_error_type());
+        assert_eq!(None, error.transceiver_status());
+    }
+
+    #[test]
+    fn lost_arbitration() {
+        let frame = Frame::error(ErrorClass::LOST_ARBITRATION, [0x0A, 0, 0, 0, 0, 0, 0, 0]);
+        let error = frame.decode_error().unwrap();
+
+        assert_eq!(ErrorClass::LOST_ARBITRATION, error.class());
+        assert_eq!(Some(0x0A), error.arbitration_lost_bit());
+        assert_eq!(None, error.controller_status());
+        assert_eq!(None, error.error_counters());
+    }
+
+    #[test]
+    fn protocol_violation() {
+        let class = ErrorClass::PROTOCOL | ErrorClass::BUS_ERROR;
+        let frame = Frame::error(class, [0x00, 0x00, 0x84, 0x0A, 0x00, 0x00, 0x00, 0x00]);
+        let error = frame.decode_error().unwrap();
+
+        assert_eq!(
+            Some(ProtocolErrorType::STUFF | ProtocolErrorType::TX),
+            error.protocol_error_type()
+        );
+        assert_eq!(
+            Some(ProtocolErrorLocation::Data),
+            error.protocol_error_location()
+        );
+    }
+
+    #[test]
+    fn non_error_frame() {
+        let id = Id::Standard(StandardId::ZERO);
+        let frame = Frame::from_static(id, &[0x00, 0x20]);
+        assert_eq!(None, frame.decode_error());
+    }
+}
@@ -0,0 +1,986 @@
+//! ISO-TP (ISO 15765-2) transport protocol support.
+//!
+//! ISO-TP allows sending payloads larger than what fits in a single CAN frame by segmenting them
+//! across multiple frames.  Each frame starts with protocol control information (PCI), which
+//! identifies the type of the frame, and carries the information needed to reassemble the payload.
+
+use core::{fmt, iter, time::Duration};
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::identifier::Id;
+
+use super::Frame;
+
+const PCI_TYPE_SINGLE_FRAME: u8 = 0x0;
+const PCI_TYPE_FIRST_FRAME: u8 = 0x1;
+const PCI_TYPE_CONSECUTIVE_FRAME: u8 = 0x2;
+const PCI_TYPE_FLOW_CONTROL: u8 = 0x3;
+
+// Largest payload length that can be encoded in the low nibble of a "Single Frame" without using the
+// escape sequence.
+const SINGLE_FRAME_MAX_LEN: u8 = 7;
+
+// Largest payload length that can be encoded in the 12-bit length of a "First Frame" without using
+// the escape sequence.
+const FIRST_FRAME_MAX_LEN: u32 = 0xFFF;
+
+// Smallest payload length that requires segmentation when using classic CAN.
+const FIRST_FRAME_MIN_LEN: u32 = 8;
+
+// Number of payload bytes carried by a classic "First Frame" and "Consecutive Frame", respectively.
+const FIRST_FRAME_DATA_LEN: usize = 6;
+const FIRST_FRAME_ESCAPED_DATA_LEN: usize = 2;
+const CONSECUTIVE_FRAME_MAX_DATA_LEN: usize = 7;
+
+/// Flow status of an ISO-TP "Flow Control" frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlowStatus {
+    /// The sender may continue to send consecutive frames.
+    ContinueToSend,
+
+    /// The sender must wait for another flow control frame before continuing.
+    Wait,
+
+    /// The payload is too large for the receiver, and the transfer is aborted.
+    Overflow,
+}
+
+impl FlowStatus {
+    const fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            0x0 => Some(Self::ContinueToSend),
+            0x1 => Some(Self::Wait),
+            0x2 => Some(Self::Overflow),
+            _ => None,
+        }
+    }
+
+    const fn as_raw(self) -> u8 {
+        match self {
+            Self::ContinueToSend => 0x0,
+            Self::Wait => 0x1,
+            Self::Overflow => 0x2,
+        }
+    }
+}
+
+/// Converts the encoded minimum separation time (STmin) of a "Flow Control" frame into a
+/// [`Duration`].
+///
+/// Values from `0x00` to `0x7F` encode a separation time of 0 to 127 milliseconds, and values from
+/// `0xF1` to `0xF9` encode a separation time of 100 to 900 microseconds.
+///
+/// # Errors
+///
+/// If `st_min` is one of the reserved values, then `None` is returned.
+pub const fn st_min_to_duration(st_min: u8) -> Option<Duration> {
+    match st_min {
+        0x00..=0x7F => Some(Duration::from_millis(st_min as u64)),
+        0xF1..=0xF9 => Some(Duration::from_micros((st_min - 0xF0) as u64 * 100)),
+        _ => None,
+    }
+}
+
+/// Protocol control information (PCI) of an ISO-TP frame.
+///
+/// Every ISO-TP frame is one of four types, each of which encodes different information in the
+/// first one or more bytes of the frame data.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IsoTpPci {
+    /// A "Single Frame", which carries an entire payload of `len` bytes.
+    SingleFrame {
+        /// Length of the payload.
+        len: u8,
+    },
+
+    /// A "First Frame", which carries the start of a payload of `len` bytes.
+    FirstFrame {
+        /// Total length of the payload.
+        len: u32,
+    },
+
+    /// A "Consecutive Frame", which carries the next segment of a payload.
+    ConsecutiveFrame {
+        /// Sequence number of the frame, from 0 to 15.
+        seq: u8,
+    },
+
+    /// A "Flow Control" frame, which is sent by the receiver of a payload to control how
+    /// consecutive frames are sent.
+    FlowControl {
+        /// Flow status.
+        status: FlowStatus,
+
+        /// Number of consecutive frames that may be sent before waiting for another flow control
+        /// frame, where zero indicates that all remaining frames may be sent.
+        block_size: u8,
+
+        /// Minimum separation time between consecutive frames, in its encoded form.
+        st_min: u8,
+    },
+}
+
+/// Error that occurs when reassembling an ISO-TP payload.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IsoTpError {
+    /// The frame is not a well-formed ISO-TP frame.
+    Malformed,
+
+    /// The frame is not expected in the current state of the transfer.
+    ///
+    /// This occurs when a "Consecutive Frame" is received without a transfer in progress, or when a
+    /// "Flow Control" frame is received, as flow control frames are only ever sent to the sender of
+    /// a payload.
+    UnexpectedFrame,
+
+    /// A "Consecutive Frame" was received out of sequence.
+    OutOfSequence {
+        /// Sequence number that was expected.
+        expected: u8,
+
+        /// Sequence number that was received.
+        actual: u8,
+    },
+
+    /// The length of the payload declared by a "First Frame" exceeds the maximum payload length.
+    Overflow {
+        /// Length of the payload that was declared.
+        len: u32,
+
+        /// Maximum payload length.
+        max_len: u32,
+    },
+}
+
+impl fmt::Display for IsoTpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed => write!(f, "malformed ISO-TP frame"),
+            Self::UnexpectedFrame => write!(f, "unexpected ISO-TP frame"),
+            Self::OutOfSequence { expected, actual } => write!(
+                f,
+                "consecutive frame out of sequence: expected {}, got {}",
+                expected, actual
+            ),
+            Self::Overflow { len, max_len } => write!(
+                f,
+                "payload length of {} bytes exceeds the maximum of {} bytes",
+                len, max_len
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for IsoTpError {}
+
+/// Reassembles ISO-TP payloads from a stream of frames.
+///
+/// Frames are pushed into the reassembler one at a time, and once all of the frames of a payload
+/// have been received, the payload is returned.  A "Single Frame" immediately results in a
+/// payload, while a "First Frame" starts a new transfer, which completes once enough "Consecutive
+/// Frames" have been received to reach the length declared by the "First Frame".
+///
+/// Frames are often padded out to a fixed length, so any data in the final "Consecutive Frame"
+/// beyond the declared length is treated as padding and discarded.
+///
+/// As specified by ISO 15765-2, receiving a "Single Frame" or "First Frame" while a transfer is in
+/// progress aborts the current transfer in favor of the new one.  Similarly, any error aborts the
+/// current transfer.
+#[derive(Debug)]
+pub struct IsoTpReassembler {
+    buffer: BytesMut,
+    transfer: Option<Transfer>,
+    max_len: u32,
+}
+
+#[derive(Debug)]
+struct Transfer {
+    len: usize,
+    next_seq: u8,
+}
+
+impl IsoTpReassembler {
+    /// Creates a new [`IsoTpReassembler`].
+    ///
+    /// The maximum payload length is 4095 bytes, which is the largest payload that can be sent
+    /// without using the escape sequence for "First Frames".
+    pub fn new() -> Self {
+        Self::with_max_len(FIRST_FRAME_MAX_LEN)
+    }
+
+    /// Creates a new [`IsoTpReassembler`] with the given maximum payload length.
+    ///
+    /// Any transfer declaring a payload length larger than `max_len` is rejected.
+    pub fn with_max_len(max_len: u32) -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            transfer: None,
+            max_len,
+        }
+    }
+
+    /// Whether or not a transfer is currently in progress.
+    pub fn is_in_progress(&self) -> bool {
+        self.transfer.is_some()
+    }
+
+    /// Aborts the transfer in progress, if any.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.transfer = None;
+    }
+
+    /// Pushes a frame into the reassembler.
+    ///
+    /// If the frame completes a payload, the payload is returned.  Otherwise, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the frame is not a well-formed ISO-TP frame, is not expected in the current state of the
+    /// transfer, is out of sequence, or declares a payload that is too large, an error is returned
+    /// and the transfer in progress, if any, is aborted.
+    pub fn push(&mut self, frame: &Frame) -> Result<Option<Bytes>, IsoTpError> {
+        let result = self.push_inner(frame);
+        if result.is_err() {
+            self.reset();
+        }
+
+        result
+    }
+
+    fn push_inner(&mut self, frame: &Frame) -> Result<Option<Bytes>, IsoTpError> {
+        let (pci, pci_len) = parse_pci(frame.data()).ok_or(IsoTpError::Malformed)?;
+        let data = &frame.data()[pci_len..];
+
+        match pci {
+            IsoTpPci::SingleFrame { len } => {
+                self.reset();
+                Ok(Some(Bytes::copy_from_slice(&data[..usize::from(len)])))
+            }
+            IsoTpPci::FirstFrame { len } => {
+                self.reset();
+                if len > self.max_len {
+                    return Err(IsoTpError::Overflow {
+                        len,
+                        max_len: self.max_len,
+                    });
+                }
+
+                let len = len as usize;
+                self.buffer.reserve(len);
+                self.buffer.extend_from_slice(&data[..data.len().min(len)]);
+                self.transfer = Some(Transfer { len, next_seq: 1 });
+
+                Ok(None)
+            }
+            IsoTpPci::ConsecutiveFrame { seq } => {
+                let transfer = self.transfer.as_mut().ok_or(IsoTpError::UnexpectedFrame)?;
+                if seq != transfer.next_seq {
+                    return Err(IsoTpError::OutOfSequence {
+                        expected: transfer.next_seq,
+                        actual: seq,
+                    });
+                }
+
+                let remaining = transfer.len - self.buffer.len();
+                self.buffer
+                    .extend_from_slice(&data[..data.len().min(remaining)]);
+                transfer.next_seq = (transfer.next_seq + 1) & 0x0F;
+
+                if self.buffer.len() == transfer.len {
+                    self.transfer = None;
+                    Ok(Some(self.buffer.split().freeze()))
+                } else {
+                    Ok(None)
+                }
+            }
+            IsoTpPci::FlowControl { .. } => Err(IsoTpError::UnexpectedFrame),
+        }
+    }
+}
+
+impl Default for IsoTpReassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Callbacks for each type of ISO-TP frame.
+///
+/// Used with [`Frame::dispatch_isotp`], which decodes the PCI of a frame and calls the method
+/// matching its type with the decoded fields.  This allows transport state machines to handle each
+/// type of frame without decoding the PCI themselves.  All methods do nothing by default, so
+/// observers only need to implement the methods for the frame types they are interested in.
+pub trait IsoTpObserver {
+    /// Called for a "Single Frame", with the payload it carries.
+    fn on_single(&mut self, frame: &Frame, payload: &[u8]) {
+        let _ = (frame, payload);
+    }
+
+    /// Called for a "First Frame", with the total length of the payload, and the start of the
+    /// payload carried by the frame.
+    fn on_first(&mut self, frame: &Frame, len: u32, data: &[u8]) {
+        let _ = (frame, len, data);
+    }
+
+    /// Called for a "Consecutive Frame", with its sequence number and the segment of the payload it
+    /// carries.
+    ///
+    /// The segment may include padding, as the length of the final segment is only known from the
+    /// total length of the payload.
+    fn on_consecutive(&mut self, frame: &Frame, seq: u8, data: &[u8]) {
+        let _ = (frame, seq, data);
+    }
+
+    /// Called for a "Flow Control" frame, with its flow status, block size, and encoded minimum
+    /// separation time.
+    fn on_flow_control(&mut self, frame: &Frame, status: FlowStatus, block_size: u8, st_min: u8) {
+        let _ = (frame, status, block_size, st_min);
+    }
+}
+
+impl Frame {
+    /// Decodes the ISO-TP protocol control information (PCI) of this frame.
+    ///
+    /// Both the classic encoding and the escape sequence encoding, used for CAN FD "Single Frames"
+    /// with more than seven bytes and "First Frames" with more than 4095 bytes, are supported.
+    ///
+    /// # Errors
+    ///
+    /// If this frame is not a well-formed ISO-TP frame, either because the frame type is unknown,
+    /// or because the PCI itself is invalid or is not fully present, then `None` is returned.
+    pub fn isotp_pci(&self) -> Option<IsoTpPci> {
+        parse_pci(self.data()).map(|(pci, _)| pci)
+    }
+
+    /// Decodes the ISO-TP protocol control information (PCI) of this frame, and calls the method of
+    /// `observer` matching the frame type.
+    ///
+    /// Returns `true` if a method of `observer` was called.
+    ///
+    /// # Errors
+    ///
+    /// If this frame is not a well-formed ISO-TP frame, as described in
+    /// [`isotp_pci`][Self::isotp_pci], no method is called and `false` is returned.
+    pub fn dispatch_isotp(&self, observer: &mut impl IsoTpObserver) -> bool {
+        let (pci, pci_len) = match parse_pci(self.data()) {
+            Some(parsed) => parsed,
+            None => return false,
+        };
+        let data = &self.data()[pci_len..];
+
+        match pci {
+            IsoTpPci::SingleFrame { len } => observer.on_single(self, &data[..usize::from(len)]),
+            IsoTpPci::FirstFrame { len } => observer.on_first(self, len, data),
+            IsoTpPci::ConsecutiveFrame { seq } => observer.on_consecutive(self, seq, data),
+            IsoTpPci::FlowControl {
+                status,
+                block_size,
+                st_min,
+            } => observer.on_flow_control(self, status, block_size, st_min),
+        }
+
+        true
+    }
+
+    /// Creates a new `Frame` that is compliant as an ISO-TP "First Frame".
+    ///
+    /// A "First Frame" starts the transfer of a payload that is too large to fit in a "Single
+    /// Frame", and holds the total length of the payload, `total_len`, followed by the first six
+    /// bytes of the payload.  The existing identifier is copied over to the new frame, and the first
+    /// six bytes of the existing data are used as the start of the payload.  The remainder of the
+    /// payload is sent in "Consecutive Frames", which can be created with
+    /// [`consecutive_frame`][Self::consecutive_frame].
+    ///
+    /// # Errors
+    ///
+    /// If `total_len` is too small to require segmentation (less than eight bytes) or too large to
+    /// fit in the 12-bit length field (greater than 4095 bytes), or the current frame has less than
+    /// six bytes of data or more data than `total_len`, then `None` is returned.
+    pub fn as_isotp_first_frame(&self, total_len: u16) -> Option<Self> {
+        let total_len_raw = u32::from(total_len);
+        if !(FIRST_FRAME_MIN_LEN..=FIRST_FRAME_MAX_LEN).contains(&total_len_raw)
+            || self.data.len() < FIRST_FRAME_DATA_LEN
+            || self.data.len() > usize::from(total_len)
+        {
+            return None;
+        }
+
+        let mut new_data = BytesMut::with_capacity(2 + FIRST_FRAME_DATA_LEN);
+        new_data.put_u16(u16::from(PCI_TYPE_FIRST_FRAME) << 12 | total_len);
+        new_data.extend_from_slice(&self.data[..FIRST_FRAME_DATA_LEN]);
+
+        Some(self.derive(new_data.freeze()))
+    }
+
+    /// Creates a `Frame` that is compliant as an ISO-TP "Consecutive Frame".
+    ///
+    /// A "Consecutive Frame" carries the next segment of a payload after a "First Frame", and holds
+    /// a sequence number, which starts at one for the first "Consecutive Frame" and wraps around
+    /// from 15 to zero, followed by up to seven bytes of the payload.  Only the lower four bits of
+    /// `seq` are used, so callers can simply increment the sequence number and let it wrap.
+    ///
+    /// # Errors
+    ///
+    /// If `data` is larger than seven bytes, then `None` is returned.
+    pub fn consecutive_frame(id: Id, seq: u8, data: &[u8]) -> Option<Self> {
+        if data.len() > CONSECUTIVE_FRAME_MAX_DATA_LEN {
+            return None;
+        }
+
+        let mut new_data = BytesMut::with_capacity(1 + data.len());
+        new_data.put_u8(PCI_TYPE_CONSECUTIVE_FRAME << 4 | (seq & 0x0F));
+        new_data.extend_from_slice(data);
+
+        Some(Self::new(id, new_data.freeze()))
+    }
+
+    /// Segments a payload into a sequence of ISO-TP frames.
+    ///
+    /// Payloads of up to seven bytes are sent as a single "Single Frame".  Larger payloads are sent
+    /// as a "First Frame" followed by as many "Consecutive Frames" as needed, with sequence numbers
+    /// starting at one and wrapping from 15 to zero.  Payloads larger than 4095 bytes, which do not
+    /// fit in the 12-bit length of a "First Frame", use the escape sequence where the length is
+    /// instead sent as a 32-bit value.
+    ///
+    /// The frames are not padded: see [`padded`][Self::padded] for padding each frame.
+    ///
+    /// # Errors
+    ///
+    /// If the payload is empty, or larger than 4294967295 bytes, then `None` is returned.
+    pub fn isotp_segments(id: Id, payload: &[u8]) -> Option<impl Iterator<Item = Self> + '_> {
+        let len = u32::try_from(payload.len()).ok()?;
+        if len == 0 {
+            return None;
+        }
+
+        let (first, remaining) = if len < FIRST_FRAME_MIN_LEN {
+            let single = Self::from_slice(id, payload).as_isotp_frame()?;
+            (single, &payload[payload.len()..])
+        } else if len <= FIRST_FRAME_MAX_LEN {
+            let mut data = BytesMut::with_capacity(2 + FIRST_FRAME_DATA_LEN);
+            data.put_u16(u16::from(PCI_TYPE_FIRST_FRAME) << 12 | len as u16);
+            data.extend_from_slice(&payload[..FIRST_FRAME_DATA_LEN]);
+            (
+                Self::new(id, data.freeze()),
+                &payload[FIRST_FRAME_DATA_LEN..],
+            )
+        } else {
+            let mut data = BytesMut::with_capacity(6 + FIRST_FRAME_ESCAPED_DATA_LEN);
+            data.put_u16(u16::from(PCI_TYPE_FIRST_FRAME) << 12);
+            data.put_u32(len);
+            data.extend_from_slice(&payload[..FIRST_FRAME_ESCAPED_DATA_LEN]);
+            (
+                Self::new(id, data.freeze()),
+                &payload[FIRST_FRAME_ESCAPED_DATA_LEN..],
+            )
+        };
+
+        let consecutive = remaining
+            .chunks(CONSECUTIVE_FRAME_MAX_DATA_LEN)
+            .enumerate()
+            .map(move |(i, chunk)| {
+                // Sequence numbers start at one, and only the lower four bits are used.
+                let seq = (i + 1) as u8;
+                Self::consecutive_frame(id, seq, chunk).expect("chunks must be at most seven bytes")
+            });
+
+        Some(iter::once(first).chain(consecutive))
+    }
+
+    /// Creates a `Frame` that is compliant as an ISO-TP "Flow Control" frame.
+    ///
+    /// A "Flow Control" frame is sent by the receiver of a payload after receiving a "First Frame",
+    /// and controls how the sender transmits the remaining "Consecutive Frames": `block_size` is the
+    /// number of frames that may be sent before waiting for another "Flow Control" frame, where
+    /// zero means all remaining frames may be sent, and `st_min` is the encoded minimum separation
+    /// time between frames, as described in [`st_min_to_duration`].
+    pub fn flow_control(id: Id, status: FlowStatus, block_size: u8, st_min: u8) -> Self {
+        let data = [
+            PCI_TYPE_FLOW_CONTROL << 4 | status.as_raw(),
+            block_size,
+            st_min,
+        ];
+
+        Self::new(id, Bytes::copy_from_slice(&data))
+    }
+}
+
+/// Parses the PCI at the start of the given data.
+///
+/// Returns the PCI, along with the number of bytes of `data` used to encode it.
+pub(super) fn parse_pci(data: &[u8]) -> Option<(IsoTpPci, usize)> {
+    let first = *data.first()?;
+    let (pci_type, low_nibble) = (first >> 4, first & 0x0F);
+
+    match pci_type {
+        PCI_TYPE_SINGLE_FRAME => {
+            // Classic single frames encode lengths of up to seven bytes in the low nibble, and the
+            // escape sequence is only used for CAN FD single frames longer than that.
+            let (len, pci_len) = match low_nibble {
+                0 => match *data.get(1)? {
+                    len if len > SINGLE_FRAME_MAX_LEN => (len, 2),
+                    _ => return None,
+                },
+                1..=SINGLE_FRAME_MAX_LEN => (low_nibble, 1),
+                _ => return None,
+            };
+
+            if data.len() - pci_len < usize::from(len) {
+                return None;
+            }
+
+            Some((IsoTpPci::SingleFrame { len }, pci_len))
+        }
+        PCI_TYPE_FIRST_FRAME => {
+            let short_len = u32::from(low_nibble) << 8 | u32::from(*data.get(1)?);
+            if short_len == 0 {
+                let escaped_len = data.get(2..6)?;
+                let len = u32::from_be_bytes([
+                    escaped_len[0],
+                    escaped_len[1],
+                    escaped_len[2],
+                    escaped_len[3],
+                ]);
+
+                if len <= FIRST_FRAME_MAX_LEN {
+                    return None;
+                }
+
+                Some((IsoTpPci::FirstFrame { len }, 6))
+            } else {
+                if short_len < FIRST_FRAME_MIN_LEN {
+                    return None;
+                }
+
+                Some((IsoTpPci::FirstFrame { len: short_len }, 2))
+            }
+        }
+        PCI_TYPE_CONSECUTIVE_FRAME => Some((IsoTpPci::ConsecutiveFrame { seq: low_nibble }, 1)),
+        PCI_TYPE_FLOW_CONTROL => {
+            let status = FlowStatus::from_raw(low_nibble)?;
+            let block_size = *data.get(1)?;
+            let st_min = *data.get(2)?;
+
+            Some((
+                IsoTpPci::FlowControl {
+                    status,
+                    block_size,
+                    st_min,
+                },
+                3,
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        frame::Frame,
+        identifier::{Id, StandardId},
+    };
+
+    use std::{time::Duration, vec::Vec};
+
+    use super::{
+        st_min_to_duration, FlowStatus, IsoTpError, IsoTpObserver, IsoTpPci, IsoTpReassembler,
+    };
+
+    fn frame(data: &'static [u8]) -> Frame {
+        Frame::from_static(Id::Standard(StandardId::new(0x7E8).unwrap()), data)
+    }
+
+    #[test]
+    fn single_frame() {
+        let pci = frame(&[0x02, 0x41, 0x0C]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::SingleFrame { len: 2 }), pci);
+
+        // CAN FD escape sequence.
+        let mut data = vec![0x00, 0x0A];
+        data.extend_from_slice(&[0xAA; 10]);
+        let frame_fd = Frame::new(frame(&[]).id(), data.into());
+        assert_eq!(
+            Some(IsoTpPci::SingleFrame { len: 10 }),
+            frame_fd.isotp_pci()
+        );
+        assert_eq!(Some(&[0xAA; 10][..]), frame_fd.decode_isotp_single());
+
+        // Length exceeds available data, and zero length.
+        assert_eq!(None, frame(&[0x03, 0x41, 0x0C]).isotp_pci());
+        assert_eq!(None, frame(&[0x00, 0x00]).isotp_pci());
+        assert_eq!(None, frame(&[0x00]).isotp_pci());
+    }
+
+    #[test]
+    fn first_frame() {
+        let pci = frame(&[0x10, 0x14, 0x49, 0x02, 0x01, 0x31, 0x44, 0x34]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::FirstFrame { len: 0x14 }), pci);
+
+        let pci = frame(&[0x1F, 0xFF, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::FirstFrame { len: 0xFFF }), pci);
+
+        // Escape sequence.
+        let pci = frame(&[0x10, 0x00, 0x00, 0x01, 0x00, 0x00, 0xAA, 0xAA]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::FirstFrame { len: 0x10000 }), pci);
+
+        // Escape sequence with a length that doesn't require it, a length too short to require
+        // segmentation, and a truncated length.
+        assert_eq!(
+            None,
+            frame(&[0x10, 0x00, 0x00, 0x00, 0x00, 0x14, 0xAA, 0xAA]).isotp_pci()
+        );
+        assert_eq!(None, frame(&[0x10, 0x07, 0xAA, 0xAA]).isotp_pci());
+        assert_eq!(None, frame(&[0x10]).isotp_pci());
+        assert_eq!(None, frame(&[0x10, 0x00, 0x00, 0x01]).isotp_pci());
+    }
+
+    #[test]
+    fn consecutive_frame() {
+        let pci = frame(&[0x21, 0x31, 0x44, 0x34]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::ConsecutiveFrame { seq: 1 }), pci);
+
+        let pci = frame(&[0x2F, 0x31]).isotp_pci();
+        assert_eq!(Some(IsoTpPci::ConsecutiveFrame { seq: 15 }), pci);
+    }
+
+    #[test]
+    fn flow_control() {
+        let pci = frame(&[0x30, 0x08, 0x14]).isotp_pci();
+        assert_eq!(
+            Some(IsoTpPci::FlowControl {
+                status: FlowStatus::ContinueToSend,
+                block_size: 8,
+                st_min: 0x14
+            }),
+            pci
+        );
+
+        let pci = frame(&[0x31, 0x00, 0x00, 0xCC, 0xCC]).isotp_pci();
+        assert_eq!(
+            Some(IsoTpPci::FlowControl {
+                status: FlowStatus::Wait,
+                block_size: 0,
+                st_min: 0
+            }),
+            pci
+        );
+
+        let pci = frame(&[0x32, 0x00, 0x00]).isotp_pci();
+        assert_eq!(
+            Some(IsoTpPci::FlowControl {
+                status: FlowStatus::Overflow,
+                block_size: 0,
+                st_min: 0
+            }),
+            pci
+        );
+
+        // Unknown flow status, and truncated flow control.
+        assert_eq!(None, frame(&[0x33, 0x00, 0x00]).isotp_pci());
+        assert_eq!(None, frame(&[0x30, 0x00]).isotp_pci());
+    }
+
+    #[test]
+    fn unknown_type() {
+        assert_eq!(None, frame(&[0x40, 0x00, 0x00]).isotp_pci());
+        assert_eq!(None, frame(&[]).isotp_pci());
+    }
+
+    #[test]
+    fn encode_first_frame() {
+        let payload = frame(&[0x49, 0x02, 0x01, 0x31, 0x44, 0x34, 0x47, 0x50]);
+
+        let first = payload.as_isotp_first_frame(0x14).unwrap();
+        assert_eq!(
+            &[0x10, 0x14, 0x49, 0x02, 0x01, 0x31, 0x44, 0x34],
+            first.data()
+        );
+        assert_eq!(payload.id(), first.id());
+        assert_eq!(Some(IsoTpPci::FirstFrame { len: 0x14 }), first.isotp_pci());
+
+        let first = payload.as_isotp_first_frame(0xFFF).unwrap();
+        assert_eq!(&[0x1F, 0xFF], &first.data()[..2]);
+
+        // Lengths that don't require segmentation, or don't fit in 12 bits, as well as not enough
+        // data, or more data than the declared length.
+        assert!(payload.as_isotp_first_frame(7).is_none());
+        assert!(payload.as_isotp_first_frame(0x1000).is_none());
+        assert!(frame(&[0x49, 0x02, 0x01])
+            .as_isotp_first_frame(0x14)
+            .is_none());
+        assert!(frame(&[0xAA; 10]).as_isotp_first_frame(9).is_none());
+    }
+
+    #[test]
+    fn encode_consecutive_frame() {
+        let id = frame(&[]).id();
+
+        let consecutive = Frame::consecutive_frame(id, 1, &[0x47, 0x50, 0x31]).unwrap();
+        assert_eq!(&[0x21, 0x47, 0x50, 0x31], consecutive.data());
+        assert_eq!(
+            Some(IsoTpPci::ConsecutiveFrame { seq: 1 }),
+            consecutive.isotp_pci()
+        );
+
+        // Sequence numbers wrap around.
+        let consecutive = Frame::consecutive_frame(id, 16, &[0x47]).unwrap();
+        assert_eq!(&[0x20, 0x47], consecutive.data());
+
+        assert!(Frame::consecutive_frame(id, 1, &[0xAA; 8]).is_none());
+    }
+
+    fn segment(payload: &[u8]) -> Vec<Frame> {
+        let id = frame(&[]).id();
+        let whole = Frame::new(id, payload.to_vec().into());
+        let mut frames = vec![whole.as_isotp_first_frame(payload.len() as u16).unwrap()];
+
+        for (i, chunk) in payload[6..].chunks(7).enumerate() {
+            let seq = (i + 1) as u8;
+            frames.push(Frame::consecutive_frame(id, seq, chunk).unwrap());
+        }
+
+        frames
+    }
+
+    #[test]
+    fn reassemble() {
+        let payload = (0..=255u8).cycle().take(150).collect::<Vec<_>>();
+        let frames = segment(&payload);
+        let mut reassembler = IsoTpReassembler::new();
+
+        let (last, rest) = frames.split_last().unwrap();
+        for frame in rest {
+            assert_eq!(Ok(None), reassembler.push(frame));
+            assert!(reassembler.is_in_progress());
+        }
+
+        let reassembled = reassembler.push(last).unwrap().unwrap();
+        assert_eq!(&payload[..], &reassembled[..]);
+        assert!(!reassembler.is_in_progress());
+    }
+
+    #[test]
+    fn reassemble_single_frame() {
+        let mut reassembler = IsoTpReassembler::new();
+        let reassembled = reassembler
+            .push(&frame(&[0x02, 0x41, 0x0C, 0xCC, 0xCC]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(&[0x41, 0x0C], &reassembled[..]);
+    }
+
+    #[test]
+    fn reassemble_discards_padding() {
+        let mut reassembler = IsoTpReassembler::new();
+        let first = frame(&[0x10, 0x08, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        let consecutive = frame(&[0x21, 0x07, 0x08, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC]);
+
+        assert_eq!(Ok(None), reassembler.push(&first));
+        let reassembled = reassembler.push(&consecutive).unwrap().unwrap();
+        assert_eq!(
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+            &reassembled[..]
+        );
+    }
+
+    #[test]
+    fn reassemble_errors() {
+        let payload = [0xAA; 30];
+        let frames = segment(&payload);
+        let mut reassembler = IsoTpReassembler::new();
+
+        // Consecutive frame without a transfer in progress.
+        assert_eq!(
+            Err(IsoTpError::UnexpectedFrame),
+            reassembler.push(&frames[1])
+        );
+
+        // Out of sequence consecutive frame, which aborts the transfer.
+        assert_eq!(Ok(None), reassembler.push(&frames[0]));
+        assert_eq!(
+            Err(IsoTpError::OutOfSequence {
+                expected: 1,
+                actual: 2
+            }),
+            reassembler.push(&frames[2])
+        );
+        assert!(!reassembler.is_in_progress());
+
+        // Flow control frames, and malformed frames.
+        assert_eq!(
+            Err(IsoTpError::UnexpectedFrame),
+            reassembler.push(&frame(&[0x30, 0x00, 0x00]))
+        );
+        assert_eq!(
+            Err(IsoTpError::Malformed),
+            reassembler.push(&frame(&[0x40]))
+        );
+
+        // Declared length larger than the maximum.
+        let mut reassembler = IsoTpReassembler::with_max_len(16);
+        assert_eq!(
+            Err(IsoTpError::Overflow {
+                len: 30,
+                max_len: 16
+            }),
+            reassembler.push(&frames[0])
+        );
+    }
+
+    #[test]
+    fn encode_flow_control() {
+        let id = frame(&[]).id();
+        let fc = Frame::flow_control(id, FlowStatus::Wait, 8, 0x14);
+        assert_eq!(&[0x31, 0x08, 0x14], fc.data());
+        assert_eq!(
+            Some(IsoTpPci::FlowControl {
+                status: FlowStatus::Wait,
+                block_size: 8,
+                st_min: 0x14,
+            }),
+            fc.isotp_pci()
+        );
+
+        let fc = Frame::flow_control(id, FlowStatus::Overflow, 0, 0);
+        assert_eq!(&[0x32, 0x00, 0x00], fc.data());
+    }
+
+    #[test]
+    fn st_min() {
+        assert_eq!(Some(Duration::ZERO), st_min_to_duration(0x00));
+        assert_eq!(Some(Duration::from_millis(20)), st_min_to_duration(0x14));
+        assert_eq!(Some(Duration::from_millis(127)), st_min_to_duration(0x7F));
+        assert_eq!(Some(Duration::from_micros(100)), st_min_to_duration(0xF1));
+        assert_eq!(Some(Duration::from_micros(900)), st_min_to_duration(0xF9));
+
+        for reserved in (0x80..=0xF0).chain(0xFA..=0xFF) {
+            assert_eq!(None, st_min_to_duration(reserved));
+        }
+    }
+
+    #[test]
+    fn isotp_segments() {
+        let id = frame(&[]).id();
+
+        for len in [1, 7, 8, 13, 14, 100, 4095] {
+            let payload = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let mut reassembler = IsoTpReassembler::new();
+            let mut reassembled = None;
+            for frame in Frame::isotp_segments(id, &payload).unwrap() {
+                assert!(frame.len() <= 8);
+                assert!(reassembled.is_none());
+                reassembled = reassembler.push(&frame).unwrap();
+            }
+
+            assert_eq!(Some(&payload[..]), reassembled.as_deref());
+        }
+
+        let frames = Frame::isotp_segments(id, &[0x01, 0x02])
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(1, frames.len());
+        assert_eq!(&[0x02, 0x01, 0x02], frames[0].data());
+
+        let payload = [0xAA; 20];
+        let frames = Frame::isotp_segments(id, &payload)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(3, frames.len());
+        assert_eq!(&[0x10, 0x14], &frames[0].data()[..2]);
+        assert_eq!(0x21, frames[1].data()[0]);
+        assert_eq!(0x22, frames[2].data()[0]);
+
+        assert!(Frame::isotp_segments(id, &[]).is_none());
+    }
+
+    #[test]
+    fn isotp_segments_escaped_length() {
+        let id = frame(&[]).id();
+        let payload = (0..5000).map(|i| i as u8).collect::<Vec<_>>();
+        let frames = Frame::isotp_segments(id, &payload)
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            &[0x10, 0x00, 0x00, 0x00, 0x13, 0x88, 0x00, 0x01],
+            frames[0].data()
+        );
+        assert_eq!(
+            Some(IsoTpPci::FirstFrame { len: 5000 }),
+            frames[0].isotp_pci()
+        );
+
+        // Sequence numbers wrap around from 15 to zero.
+        assert_eq!(0x2F, frames[15].data()[0]);
+        assert_eq!(0x20, frames[16].data()[0]);
+
+        let mut reassembler = IsoTpReassembler::with_max_len(5000);
+        let mut reassembled = None;
+        for frame in &frames {
+            reassembled = reassembler.push(frame).unwrap();
+        }
+        assert_eq!(Some(&payload[..]), reassembled.as_deref());
+    }
+
+    #[test]
+    fn dispatch() {
+        #[derive(Debug, Default, PartialEq)]
+        struct Recorder {
+            events: Vec<(&'static str, u32, Vec<u8>)>,
+        }
+
+        impl IsoTpObserver for Recorder {
+            fn on_single(&mut self, _: &Frame, payload: &[u8]) {
+                self.events.push(("single", 0, payload.to_vec()));
+            }
+
+            fn on_first(&mut self, _: &Frame, len: u32, data: &[u8]) {
+                self.events.push(("first", len, data.to_vec()));
+            }
+
+            fn on_consecutive(&mut self, _: &Frame, seq: u8, data: &[u8]) {
+                self.events
+                    .push(("consecutive", u32::from(seq), data.to_vec()));
+            }
+
+            fn on_flow_control(
+                &mut self,
+                _: &Frame,
+                status: FlowStatus,
+                block_size: u8,
+                st_min: u8,
+            ) {
+                assert_eq!(FlowStatus::Wait, status);
+                self.events
+                    .push(("flow_control", 0, vec![block_size, st_min]));
+            }
+        }
+
+        let mut recorder = Recorder::default();
+        assert!(frame(&[0x02, 0x41, 0x0C, 0xCC]).dispatch_isotp(&mut recorder));
+        assert!(frame(&[0x10, 0x0A, 1, 2, 3, 4, 5, 6]).dispatch_isotp(&mut recorder));
+        assert!(frame(&[0x21, 7, 8, 9, 10]).dispatch_isotp(&mut recorder));
+        assert!(frame(&[0x31, 0x08, 0x14]).dispatch_isotp(&mut recorder));
+        assert!(!frame(&[0x40]).dispatch_isotp(&mut recorder));
+
+        assert_eq!(
+            vec![
+                ("single", 0, vec![0x41, 0x0C]),
+                ("first", 10, vec![1, 2, 3, 4, 5, 6]),
+                ("consecutive", 1, vec![7, 8, 9, 10]),
+                ("flow_control", 0, vec![0x08, 0x14]),
+            ],
+            recorder.events
+        );
+
+        // Observers only need to implement the methods they are interested in.
+        struct Ignore;
+        impl IsoTpObserver for Ignore {}
+        assert!(frame(&[0x02, 0x41, 0x0C]).dispatch_isotp(&mut Ignore));
+    }
+}
@@ -0,0 +1,160 @@
+use core::borrow::Borrow;
+
+use crate::identifier::{Filter, FilterSet};
+
+use super::Frame;
+
+/// Extension methods for iterators of frames.
+///
+/// This is implemented for any iterator that yields either [`Frame`] or `&Frame`.
+pub trait FrameIteratorExt: Iterator + Sized
+where
+    Self::Item: Borrow<Frame>,
+{
+    /// Creates an iterator which only yields the frames that match the given filter.
+    ///
+    /// See [`Frame::matches`] for how frames are matched.
+    fn matching(self, filter: Filter) -> Matching<Self> {
+        Matching {
+            inner: self,
+            filter,
+        }
+    }
+
+    /// Creates an iterator which only yields the frames that match any filter in the given set.
+    ///
+    /// See [`FilterSet::matches`] for how frames are matched.
+    fn matching_any(self, filters: &FilterSet) -> MatchingAny<'_, Self> {
+        MatchingAny {
+            inner: self,
+            filters,
+        }
+    }
+}
+
+impl<I> FrameIteratorExt for I
+where
+    I: Iterator,
+    I::Item: Borrow<Frame>,
+{
+}
+
+/// Iterator that only yields the frames that match a filter.
+///
+/// Created by [`FrameIteratorExt::matching`].
+#[derive(Clone, Debug)]
+pub struct Matching<I> {
+    inner: I,
+    filter: Filter,
+}
+
+impl<I> Iterator for Matching<I>
+where
+    I: Iterator,
+    I::Item: Borrow<Frame>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let filter = &self.filter;
+        self.inner.find(|frame| frame.borrow().matches(filter))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+/// Iterator that only yields the frames that match any filter in a set.
+///
+/// Created by [`FrameIteratorExt::matching_any`].
+#[derive(Clone, Debug)]
+pub struct MatchingAny<'a, I> {
+    inner: I,
+    filters: &'a FilterSet,
+}
+
+impl<I> Iterator for MatchingAny<'_, I>
+where
+    I: Iterator,
+    I::Item: Borrow<Frame>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let filters = self.filters;
+        self.inner
+            .find(|frame| filters.matches(frame.borrow().id()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::identifier::{ExtendedId, Filter, FilterSet, Id, StandardId};
+
+    use super::{Frame, FrameIteratorExt};
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    fn frames() -> Vec<Frame> {
+        vec![
+            Frame::from_static(sid(0x7E8), &[0x01]),
+            Frame::remote(sid(0x7E8), 1).unwrap(),
+            Frame::from_static(sid(0x7E9), &[0x02]),
+            Frame::from_static(Id::Extended(ExtendedId::new(0x7E8).unwrap()), &[0x03]),
+            Frame::from_static(sid(0x123), &[0x04]),
+            Frame::from_static(sid(0x7E8), &[0x05]),
+        ]
+    }
+
+    #[test]
+    fn matching() {
+        let frames = frames();
+        let filter = Filter::from_identity(sid(0x7E8));
+
+        let matched = frames.iter().matching(filter).collect::<Vec<_>>();
+        let expected = frames
+            .iter()
+            .filter(|f| filter.matches_frame(f))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, matched);
+        assert_eq!(2, matched.len());
+        assert_eq!(&[0x01], matched[0].data());
+        assert_eq!(&[0x05], matched[1].data());
+
+        // Owned frames are supported as well.
+        let matched = frames
+            .into_iter()
+            .matching(filter.allow_rtr_frames())
+            .count();
+        assert_eq!(3, matched);
+    }
+
+    #[test]
+    fn matching_any() {
+        let frames = frames();
+        let filters = [
+            Filter::from_identity(sid(0x7E9)),
+            Filter::from_identity(sid(0x123)),
+        ]
+        .into_iter()
+        .collect::<FilterSet>();
+
+        let matched = frames
+            .iter()
+            .matching_any(&filters)
+            .map(|frame| frame.data()[0])
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0x02, 0x04], matched);
+
+        assert_eq!(0, frames.iter().matching_any(&FilterSet::new()).count());
+    }
+}
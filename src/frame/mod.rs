@@ -0,0 +1,2024 @@
+//! CAN frame.
+//!
+//! Provides the [`Frame`] type, as well as helpers for working with frame payloads.
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    ops::{Bound, RangeBounds},
+    time::Duration,
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{
+    constants::{fd_dlc_to_len, len_to_fd_dlc, ErrorClass, FdFlags, FrameStatus, IdentifierFlags},
+    identifier::{Filter, Id, StandardId},
+};
+
+mod builder;
+pub use self::builder::*;
+
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+pub use self::cache::*;
+
+mod error_frame;
+pub use self::error_frame::*;
+
+mod iter;
+pub use self::iter::*;
+
+mod multiplex;
+pub use self::multiplex::*;
+
+mod signal;
+pub use self::signal::*;
+
+mod validate;
+pub use self::validate::*;
+
+#[cfg(feature = "socketcan-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "socketcan-compat")))]
+pub use crate::interop::socketcan::SocketCanFrameError;
+
+pub mod candump;
+pub mod isotp;
+pub mod slcan;
+
+// Maximum number of data bytes in a classic CAN frame.
+pub(crate) const CLASSIC_MAX_DATA_LEN: usize = 8;
+
+// Maximum number of data bytes in a CAN FD frame.
+pub(crate) const FD_MAX_DATA_LEN: usize = 64;
+
+// Number of bits in the arbitration phase of a CAN FD frame prior to switching to the data phase,
+// from the start-of-frame bit up to and including the bit rate switch bit.
+const FD_ARB_PHASE_BITS_STANDARD: u64 = 17;
+const FD_ARB_PHASE_BITS_EXTENDED: u64 = 36;
+
+// Number of bits in the arbitration phase of a CAN FD frame after switching back from the data
+// phase: CRC delimiter, ACK slot, ACK delimiter, end-of-frame, and interframe space.
+const FD_ARB_PHASE_TRAILER_BITS: u64 = 13;
+
+// Number of bits in the data phase of a CAN FD frame, excluding the data field itself: error state
+// indicator, DLC, stuff count, and then the CRC and its fixed stuff bits, which differ in length
+// depending on whether or not the payload is larger than 16 bytes.
+const FD_DATA_PHASE_BITS_CRC17: u64 = 1 + 4 + 4 + 17 + 6;
+const FD_DATA_PHASE_BITS_CRC21: u64 = 1 + 4 + 4 + 21 + 7;
+
+// Number of bits in a classic CAN frame, excluding the data field, that are subject to bit stuffing:
+// start-of-frame, arbitration field, control field, and CRC sequence.
+const CLASSIC_STUFFED_BITS_STANDARD: u32 = 1 + 11 + 1 + 1 + 1 + 4 + 15;
+const CLASSIC_STUFFED_BITS_EXTENDED: u32 = 1 + 11 + 1 + 1 + 18 + 1 + 2 + 4 + 15;
+
+// Number of bits at the end of a classic CAN frame that are not subject to bit stuffing: CRC
+// delimiter, ACK slot, ACK delimiter, end-of-frame, and interframe space.
+const CLASSIC_TRAILER_BITS: u32 = 1 + 1 + 1 + 7 + 3;
+
+// Generator polynomial of the CRC used by classic CAN frames, and the mask for its 15 bits.
+const CRC15_POLYNOMIAL: u16 = 0x4599;
+const CRC15_MASK: u16 = 0x7FFF;
+
+// Offset basis and prime of the 64-bit FNV-1a hash.
+const FNV1A_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV1A_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// A CAN frame.
+///
+/// ## High-level structure
+///
+/// Logically, a CAN frame contains both an identifier and a payload.  Within the identifier,
+/// the three of the four possible frame types are encoded: data, remote, and error.
+///
+/// Additionally, while a CAN frame, as seen being transmitted over the bus, is limited to eight
+/// bytes of data, a logical frame can represent far more than that depending on whether or not any
+/// additional transport layers are used on top of CAN itself, such as [ISO-TP][isotp].
+///
+/// As `Frame` is intended to be used by code that delegates the handling of low-level CAN details
+/// to the operating system, or controller/transmitter peripherals, we focus purely on the logical
+/// use cases, which is why `Frame` could be used for pure CAN, or ISO-TP and other transport
+/// protocols, without necessarily needing to specialize the types involved.
+///
+/// ## CAN FD
+///
+/// A frame can also be marked as a CAN FD frame, which allows up to 64 bytes of data in a single
+/// frame, and carries two additional flags: the bit rate switch (BRS) flag, which indicates that
+/// the data phase of the frame is transmitted at a higher bit rate, and the error state indicator
+/// (ESI) flag, which indicates that the transmitting node is in the error passive state.
+///
+/// ## Equality
+///
+/// Frames compare equal with `==` when their logical content is the same: the identifier, the
+/// data, the CAN FD flags, and, for remote frames, the requested DLC.  Capture metadata, namely the
+/// timestamp, the channel, and the status, is ignored, so that two captures of the same message, whether at
+/// different times or on different buses, are considered the same frame.  This is usually what is
+/// wanted when deduplicating or replaying frames.
+///
+/// To also compare capture metadata, use [`eq_exact`][Frame::eq_exact].
+///
+/// [isotp]: https://en.wikipedia.org/wiki/ISO_15765-2
+#[derive(Clone, Eq)]
+pub struct Frame {
+    id: Id,
+    data: Bytes,
+    fd_flags: FdFlags,
+    requested_dlc: u8,
+    timestamp: Option<Duration>,
+    channel: Option<Channel>,
+    status: FrameStatus,
+}
+
+impl Frame {
+    /// Creates a frame from an identifier and data.
+    pub const fn new(id: Id, data: Bytes) -> Self {
+        Self {
+            id,
+            data,
+            fd_flags: FdFlags::empty(),
+            requested_dlc: 0,
+            timestamp: None,
+            channel: None,
+            status: FrameStatus::empty(),
+        }
+    }
+
+    /// Creates a CAN FD frame from an identifier and data.
+    ///
+    /// If `brs` is `true`, the bit rate switch flag is set, indicating that the data phase of the
+    /// frame should be transmitted at the data bit rate.
+    ///
+    /// Data lengths over eight bytes are limited to 12, 16, 20, 24, 32, 48, or 64 bytes, so if the
+    /// data is not one of those lengths, it is padded with zeros up to the next valid length, as it
+    /// would be when transmitted.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than 64 bytes, an error is returned.
+    pub fn new_fd(id: Id, data: Bytes, brs: bool) -> Result<Self, FrameLengthError> {
+        if data.len() > FD_MAX_DATA_LEN {
+            return Err(FrameLengthError::new(data.len(), FD_MAX_DATA_LEN));
+        }
+
+        let padded_len = fd_padded_len(data.len());
+        let data = if padded_len == data.len() {
+            data
+        } else {
+            let mut padded = BytesMut::with_capacity(padded_len);
+            padded.extend_from_slice(&data);
+            padded.resize(padded_len, 0);
+            padded.freeze()
+        };
+
+        Ok(Self {
+            id,
+            data,
+            fd_flags: if brs {
+                FdFlags::FDF.union(FdFlags::BRS)
+            } else {
+                FdFlags::FDF
+            },
+            requested_dlc: 0,
+            timestamp: None,
+            channel: None,
+            status: FrameStatus::empty(),
+        })
+    }
+
+    /// Creates a classic CAN frame from an identifier and data.
+    ///
+    /// Unlike [`new`][Self::new], which allows creating logical frames with an arbitrary amount of
+    /// data for use with transport protocols such as ISO-TP, this ensures that the frame can be
+    /// transmitted as a single classic CAN frame.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than eight bytes, an error is returned.
+    pub fn try_new_classic(id: Id, data: Bytes) -> Result<Self, FrameLengthError> {
+        if data.len() > CLASSIC_MAX_DATA_LEN {
+            return Err(FrameLengthError::new(data.len(), CLASSIC_MAX_DATA_LEN));
+        }
+
+        Ok(Self::new(id, data))
+    }
+
+    /// Creates a frame from an identifier and a slice of data.
+    ///
+    /// The data is copied into a new buffer.  Like [`new`][Self::new], this allows creating logical
+    /// frames with an arbitrary amount of data.
+    pub fn from_slice(id: Id, data: &[u8]) -> Self {
+        Self::new(id, Bytes::copy_from_slice(data))
+    }
+
+    /// Creates a frame from an identifier and a vector of data.
+    ///
+    /// The vector is converted into the underlying buffer without copying the data.
+    pub fn from_vec(id: Id, data: Vec<u8>) -> Self {
+        Self::new(id, Bytes::from(data))
+    }
+
+    /// Creates a classic CAN frame from an identifier and a slice of data.
+    ///
+    /// The data is copied into a new buffer.  Like [`try_new_classic`][Self::try_new_classic], this
+    /// ensures that the frame can be transmitted as a single classic CAN frame.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than eight bytes, an error is returned.
+    pub fn try_from_slice(id: Id, data: &[u8]) -> Result<Self, FrameLengthError> {
+        if data.len() > CLASSIC_MAX_DATA_LEN {
+            return Err(FrameLengthError::new(data.len(), CLASSIC_MAX_DATA_LEN));
+        }
+
+        Ok(Self::from_slice(id, data))
+    }
+
+    /// Creates a CAN FD frame from an identifier and a slice of data.
+    ///
+    /// The data is copied into a new buffer, and is padded to a valid CAN FD data length in the
+    /// same way as [`new_fd`][Self::new_fd].  If `brs` is `true`, the bit rate switch flag is set.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than 64 bytes, an error is returned.
+    pub fn try_from_slice_fd(id: Id, data: &[u8], brs: bool) -> Result<Self, FrameLengthError> {
+        if data.len() > FD_MAX_DATA_LEN {
+            return Err(FrameLengthError::new(data.len(), FD_MAX_DATA_LEN));
+        }
+
+        Self::new_fd(id, Bytes::copy_from_slice(data), brs)
+    }
+
+    /// Creates a remote frame from an identifier and requested DLC.
+    ///
+    /// A remote frame carries no data, but instead requests that the node responsible for the
+    /// given identifier transmit a data frame with `dlc` bytes of data.  The remote flag is set on
+    /// the identifier.
+    ///
+    /// # Errors
+    ///
+    /// If `dlc` is greater than eight, `None` is returned.
+    pub const fn remote(id: Id, dlc: u8) -> Option<Self> {
+        if dlc as usize > CLASSIC_MAX_DATA_LEN {
+            return None;
+        }
+
+        let id = id.set_flags(id.flags().union(IdentifierFlags::REMOTE));
+        Some(Self {
+            id,
+            data: Bytes::new(),
+            fd_flags: FdFlags::empty(),
+            requested_dlc: dlc,
+            timestamp: None,
+            channel: None,
+            status: FrameStatus::empty(),
+        })
+    }
+
+    /// Creates an error frame from an error class and error details.
+    ///
+    /// The error class is encoded in the identifier, with the error flag set, and the details are
+    /// used as the data, following the layout used by SocketCAN.  See [`ErrorClass`] for which
+    /// bytes of the details are used by each error class.
+    pub fn error(class: ErrorClass, details: [u8; 8]) -> Self {
+        // All error classes fit within a standard identifier.
+        let id = StandardId::with_flags(class.bits() as u16, IdentifierFlags::ERROR)
+            .expect("error classes should fit within a standard identifier");
+
+        Self::new(Id::Standard(id), Bytes::copy_from_slice(&details))
+    }
+
+    /// Creates a frame from an identifier and static byte slice.
+    pub const fn from_static(id: Id, data: &'static [u8]) -> Self {
+        Self::new(id, Bytes::from_static(data))
+    }
+
+    /// Creates a new `Frame` after setting its error state indicator flag.
+    ///
+    /// The error state indicator only exists in CAN FD frames.  Clearing it is always allowed.
+    ///
+    /// # Errors
+    ///
+    /// If `esi` is `true` and this is not a CAN FD frame, `None` is returned.
+    pub fn with_error_state_indicator(self, esi: bool) -> Option<Self> {
+        if esi && !self.is_fd() {
+            return None;
+        }
+
+        let mut fd_flags = self.fd_flags;
+        fd_flags.set(FdFlags::ESI, esi);
+        Some(Self { fd_flags, ..self })
+    }
+
+    /// Creates a new `Frame` after setting its timestamp.
+    ///
+    /// The timestamp is typically the time at which the frame was received, as reported by the
+    /// operating system or the CAN controller, relative to whatever epoch the source of the
+    /// timestamp uses.
+    pub fn with_timestamp(self, timestamp: Duration) -> Self {
+        Self {
+            timestamp: Some(timestamp),
+            ..self
+        }
+    }
+
+    /// Gets the timestamp of this frame, if any.
+    ///
+    /// Frames created directly do not have a timestamp: it is set with
+    /// [`with_timestamp`][Self::with_timestamp], typically when receiving a frame.
+    pub const fn timestamp(&self) -> Option<Duration> {
+        self.timestamp
+    }
+
+    /// Creates a new `Frame` after setting its channel.
+    ///
+    /// The channel identifies the bus, or interface, that the frame was received on or should be
+    /// transmitted on.
+    pub fn with_channel(self, channel: Channel) -> Self {
+        Self {
+            channel: Some(channel),
+            ..self
+        }
+    }
+
+    /// Gets the channel of this frame, if any.
+    ///
+    /// Frames created directly do not have a channel: it is set with
+    /// [`with_channel`][Self::with_channel].
+    pub const fn channel(&self) -> Option<Channel> {
+        self.channel
+    }
+
+    /// Creates a new `Frame` after setting its status.
+    ///
+    /// The status records problems that occurred while receiving the frame, such as the payload
+    /// being truncated, or preceding frames having been lost.
+    pub fn with_status(self, status: FrameStatus) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Gets the status of this frame.
+    ///
+    /// Frames created directly have an empty status: it is set with
+    /// [`with_status`][Self::with_status].
+    pub const fn status(&self) -> FrameStatus {
+        self.status
+    }
+
+    /// Checks if this frame is identical to `other`, including capture metadata.
+    ///
+    /// Unlike `==`, which only compares the logical content of frames, this also requires the
+    /// timestamp, channel, and status of both frames to be the same.
+    pub fn eq_exact(&self, other: &Frame) -> bool {
+        self == other
+            && self.timestamp == other.timestamp
+            && self.channel == other.channel
+            && self.status == other.status
+    }
+
+    /// Checks if this frame carries the same message as `other`, ignoring trailing padding.
+    ///
+    /// ECUs commonly pad short payloads out to the full frame length with a fill byte, so the same
+    /// message can be sent with different amounts of padding.  This compares the identifiers of both
+    /// frames, and their data after stripping any trailing `fill` bytes from each.  Nothing else,
+    /// such as the CAN FD flags, is compared.
+    ///
+    /// As the padding cannot be distinguished from data, payloads whose last bytes are meant to be
+    /// equal to `fill` are also considered equal regardless of how many of those bytes there are.
+    pub fn eq_ignoring_padding(&self, other: &Frame, fill: u8) -> bool {
+        fn strip(data: &[u8], fill: u8) -> &[u8] {
+            let len = data.iter().rposition(|b| *b != fill).map_or(0, |i| i + 1);
+            &data[..len]
+        }
+
+        self.id == other.id && strip(&self.data, fill) == strip(&other.data, fill)
+    }
+
+    /// Gets the identifier of this frame.
+    pub const fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Gets the flags of the identifier in this frame.
+    pub const fn flags(&self) -> IdentifierFlags {
+        self.id.flags()
+    }
+
+    /// Gets the data of this frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data[..]
+    }
+
+    /// Modifies the data of this frame in place.
+    ///
+    /// The data can only be modified in place when this frame holds the only reference to its
+    /// underlying buffer, in which case `f` is called with a mutable view of the data, without
+    /// copying.  Buffers that are shared, such as with a clone of this frame, or that are static,
+    /// are never modified, and `f` is not called.
+    ///
+    /// Returns `true` if `f` was called, and `false` otherwise.  Data that cannot be modified in
+    /// place can still be replaced with [`map_data`][Self::map_data] or
+    /// [`with_data`][Self::with_data].
+    pub fn modify_data<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&mut [u8]),
+    {
+        match core::mem::take(&mut self.data).try_into_mut() {
+            Ok(mut data) => {
+                f(&mut data);
+                self.data = data.freeze();
+                true
+            }
+            Err(data) => {
+                self.data = data;
+                false
+            }
+        }
+    }
+
+    /// Consumes this frame, returning its data.
+    ///
+    /// The underlying buffer is moved out of the frame, without copying.
+    pub fn into_data(self) -> Bytes {
+        self.data
+    }
+
+    /// Creates a new `Frame` after replacing its data.
+    ///
+    /// The identifier, flags, and metadata of the frame, such as the timestamp and channel, are
+    /// kept as-is.
+    pub fn with_data(self, data: Bytes) -> Self {
+        Self { data, ..self }
+    }
+
+    /// Creates a new `Frame` after mapping its data to new data.
+    ///
+    /// The identifier, flags, and metadata of the frame, such as the timestamp and channel, are
+    /// kept as-is.  The existing data is moved into `f` without copying.
+    pub fn map_data<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(Bytes) -> Bytes,
+    {
+        self.data = f(core::mem::take(&mut self.data));
+        self
+    }
+
+    /// Gets a slice of the data of this frame.
+    ///
+    /// The returned buffer shares the underlying buffer of this frame, so no data is copied.
+    ///
+    /// # Errors
+    ///
+    /// If the range is out of bounds for the data, or its start is greater than its end, `None` is
+    /// returned.
+    pub fn payload_slice(&self, range: impl RangeBounds<usize>) -> Option<Bytes> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.data.len(),
+        };
+
+        if start > end || end > self.data.len() {
+            return None;
+        }
+
+        Some(self.data.slice(start..end))
+    }
+
+    /// Gets the number of bytes of data in this frame.
+    ///
+    /// This is the actual number of bytes in the payload, which is not necessarily the same as the
+    /// DLC of the frame: see [`dlc`][Self::dlc] for more information.
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether or not this frame has no data.
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Gets the data length code (DLC) of this frame.
+    ///
+    /// The DLC is the value of the 4-bit length field transmitted on the bus, which is not always
+    /// the same as the number of bytes of data in the frame:
+    ///
+    /// - for remote frames, the DLC is the requested DLC, as remote frames carry no data
+    /// - for classic CAN frames, the DLC is the data length, clamped to a maximum of eight, as
+    ///   logical frames may carry more data than fits in a single frame
+    /// - for CAN FD frames, the DLC is the smallest DLC that can hold the data, as data lengths over
+    ///   eight bytes are encoded in steps, such that a data length of 9 bytes has a DLC of 9, which
+    ///   represents 12 bytes
+    pub const fn dlc(&self) -> u8 {
+        if self.is_remote_frame() {
+            return self.requested_dlc;
+        }
+
+        let len = self.data.len();
+        if self.is_fd() {
+            if len > FD_MAX_DATA_LEN {
+                return 15;
+            }
+
+            match len_to_fd_dlc(len as u8) {
+                Some(dlc) => dlc,
+                None => 15,
+            }
+        } else if len > CLASSIC_MAX_DATA_LEN {
+            CLASSIC_MAX_DATA_LEN as u8
+        } else {
+            len as u8
+        }
+    }
+
+    /// Creates a new `Frame` with its data padded out using `fill`.
+    ///
+    /// Classic CAN frames are padded to eight bytes, and CAN FD frames are padded to the next valid
+    /// CAN FD data length.  Frames that are already at a valid length, as well as remote frames,
+    /// which carry no data, are returned unchanged.
+    pub fn padded(&self, fill: u8) -> Self {
+        let padded_len = if self.is_remote_frame() {
+            self.data.len()
+        } else if self.is_fd() {
+            fd_padded_len(self.data.len())
+        } else {
+            self.data.len().max(CLASSIC_MAX_DATA_LEN)
+        };
+
+        if padded_len == self.data.len() {
+            return self.clone();
+        }
+
+        let mut data = BytesMut::with_capacity(padded_len);
+        data.extend_from_slice(&self.data);
+        data.resize(padded_len, fill);
+
+        self.derive(data.freeze())
+    }
+
+    /// Copies the data of this frame into a fixed-size array suitable for a classic CAN frame.
+    ///
+    /// Returns the array, with any bytes past the end of the data set to zero, along with the
+    /// actual length of the data.  This matches the layout expected by the transmit buffers of most
+    /// classic CAN controllers.  Remote frames carry no data, and so have a length of zero.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than eight bytes, `None` is returned.
+    pub fn to_classic_bytes(&self) -> Option<([u8; 8], u8)> {
+        self.to_fixed_bytes()
+    }
+
+    /// Copies the data of this frame into a fixed-size array suitable for a CAN FD frame.
+    ///
+    /// Returns the array, with any bytes past the end of the data set to zero, along with the
+    /// actual length of the data.  The length is not rounded up to a valid CAN FD data length:
+    /// use [`padded`][Self::padded] first if the padding bytes should be counted.
+    ///
+    /// # Errors
+    ///
+    /// If the data is larger than 64 bytes, `None` is returned.
+    pub fn to_fd_bytes(&self) -> Option<([u8; 64], u8)> {
+        self.to_fixed_bytes()
+    }
+
+    fn to_fixed_bytes<const N: usize>(&self) -> Option<([u8; N], u8)> {
+        let len = self.data.len();
+        if len > N {
+            return None;
+        }
+
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&self.data);
+        Some((bytes, len as u8))
+    }
+
+    /// Whether or not this frame is valid as a single classic CAN frame.
+    ///
+    /// A classic CAN frame can hold at most eight bytes of data.
+    pub fn is_valid_classic(&self) -> bool {
+        self.data.len() <= CLASSIC_MAX_DATA_LEN
+    }
+
+    /// Whether or not this frame is valid as a single CAN FD frame.
+    ///
+    /// A CAN FD frame can hold at most 64 bytes of data, and data lengths over eight bytes are
+    /// limited to 12, 16, 20, 24, 32, 48, or 64 bytes.
+    pub fn is_valid_fd(&self) -> bool {
+        fd_padded_len(self.data.len()) == self.data.len() && self.data.len() <= FD_MAX_DATA_LEN
+    }
+
+    /// Whether or not this is a CAN FD frame.
+    pub const fn is_fd(&self) -> bool {
+        self.fd_flags.contains(FdFlags::FDF)
+    }
+
+    /// Whether or not the bit rate switch flag is set.
+    ///
+    /// This is only ever set for CAN FD frames.
+    pub const fn bit_rate_switch(&self) -> bool {
+        self.fd_flags.contains(FdFlags::BRS)
+    }
+
+    /// Whether or not the error state indicator flag is set.
+    ///
+    /// This only has meaning for CAN FD frames.
+    pub const fn error_state_indicator(&self) -> bool {
+        self.fd_flags.contains(FdFlags::ESI)
+    }
+
+    /// Gets the CAN FD flags of this frame.
+    ///
+    /// [`FdFlags::FDF`] is set for all CAN FD frames, alongside the bit rate switch and error state
+    /// indicator flags, if set.  For classic CAN frames, no flags are set.
+    pub const fn fd_flags(&self) -> FdFlags {
+        self.fd_flags
+    }
+
+    /// Whether or not this is a data frame.
+    pub const fn is_data_frame(&self) -> bool {
+        !self
+            .id
+            .flags()
+            .intersects(IdentifierFlags::ERROR.union(IdentifierFlags::REMOTE))
+    }
+
+    /// Whether or not this is a remote frame.
+    pub const fn is_remote_frame(&self) -> bool {
+        self.id.flags().contains(IdentifierFlags::REMOTE)
+    }
+
+    /// Gets the error class of this frame.
+    ///
+    /// Any bits of the identifier that do not correspond to a known error class are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If this is not an error frame, `None` is returned.
+    pub const fn error_class(&self) -> Option<ErrorClass> {
+        if self.is_error_frame() {
+            Some(ErrorClass::from_bits_truncate(self.id.as_raw()))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the requested DLC of this frame.
+    ///
+    /// # Errors
+    ///
+    /// If this is not a remote frame, `None` is returned.
+    pub const fn requested_dlc(&self) -> Option<u8> {
+        if self.is_remote_frame() {
+            Some(self.requested_dlc)
+        } else {
+            None
+        }
+    }
+
+    /// Whether or not this is an error frame.
+    pub const fn is_error_frame(&self) -> bool {
+        self.id.flags().contains(IdentifierFlags::ERROR)
+    }
+
+    /// Sets whether or not this is a remote frame.
+    ///
+    /// Remote frames carry no data, so when marking a data frame as a remote frame, its data is
+    /// cleared, and the requested DLC is set to the DLC of the data it had, limited to eight.  When
+    /// unmarking a remote frame, it becomes a data frame without any data.
+    ///
+    /// Remote frames only exist in classic CAN, so marking a CAN FD frame as a remote frame turns
+    /// it into a classic CAN frame, clearing its CAN FD flags.
+    pub fn set_remote(&mut self, remote: bool) {
+        if remote == self.is_remote_frame() {
+            return;
+        }
+
+        if remote {
+            self.requested_dlc = self.dlc().min(CLASSIC_MAX_DATA_LEN as u8);
+            self.data = Bytes::new();
+            self.fd_flags = FdFlags::empty();
+        } else {
+            self.requested_dlc = 0;
+        }
+        self.set_id_flag(IdentifierFlags::REMOTE, remote);
+    }
+
+    /// Sets whether or not this is an error frame.
+    ///
+    /// The data of the frame is kept as-is.
+    pub fn set_error(&mut self, error: bool) {
+        self.set_id_flag(IdentifierFlags::ERROR, error);
+    }
+
+    /// Sets the addressing mode of this frame.
+    ///
+    /// When switching to extended addressing, the identifier is converted to an extended identifier
+    /// with the same value, which always succeeds.  When switching to standard addressing, the
+    /// identifier is converted to a standard identifier with the same value, which is only possible
+    /// if the value is 0x7FF or less.  The remote and error flags are kept.
+    ///
+    /// Returns `false`, leaving the frame unchanged, if the identifier cannot be represented in the
+    /// requested addressing mode.
+    pub fn set_extended(&mut self, extended: bool) -> bool {
+        self.id = match (self.id, extended) {
+            (Id::Standard(id), true) => Id::Extended(id.as_extended_id()),
+            (Id::Extended(id), false) => {
+                let flags = id.flags().difference(IdentifierFlags::EXTENDED);
+                match u16::try_from(id.as_raw())
+                    .ok()
+                    .and_then(|raw| StandardId::with_flags(raw, flags))
+                {
+                    Some(id) => Id::Standard(id),
+                    None => return false,
+                }
+            }
+            (id, _) => id,
+        };
+        true
+    }
+
+    fn set_id_flag(&mut self, flag: IdentifierFlags, value: bool) {
+        let mut flags = self.id.flags();
+        flags.set(flag, value);
+        self.id = self.id.set_flags(flags);
+    }
+
+    /// Checks if this frame matches the given filter.
+    ///
+    /// This is equivalent to [`Filter::matches_frame`].
+    pub const fn matches(&self, filter: &Filter) -> bool {
+        filter.matches(self.id)
+    }
+
+    /// Gets the arbitration key of this frame.
+    ///
+    /// The key is the arbitration field of the frame as it would be transmitted, such that frames
+    /// with a lower key win arbitration over frames with a higher key.  Sorting frames by this key,
+    /// such as with `frames.sort_by_key(Frame::arbitration_key)`, orders them highest priority
+    /// first, as the bus would: lower identifiers come first, a standard identifier comes before an
+    /// extended identifier with the same upper 11 bits, and data frames come before remote frames
+    /// with the same identifier.  See [`Id::wins_arbitration_over`] for the details.
+    ///
+    /// The value of the key is otherwise unspecified, and should only be compared to the keys of
+    /// other frames.
+    pub const fn arbitration_key(&self) -> u32 {
+        self.id.arbitration_bits()
+    }
+
+    /// Creates a new `Frame` that is compliant as an ISO-TP "Single Frame".
+    ///
+    /// The existing identifier and data are copied over to the new frame, and the length of the
+    /// existing data is prepended to the data as a single byte.
+    ///
+    /// # Errors
+    ///
+    /// If the size of the data in the current frame is too large to fit in an ISO-TP "Single
+    /// Frame", then `None` is returned.
+    pub fn as_isotp_frame(&self) -> Option<Self> {
+        if self.data.len() > 7 {
+            return None;
+        }
+
+        let data_len = u8::try_from(self.data.len()).expect("self.data.len() must be less than 8");
+        let mut new_data = BytesMut::with_capacity(1 + self.data.len());
+        new_data.put_u8(data_len);
+        new_data.extend_from_slice(&self.data);
+
+        Some(self.derive(new_data.freeze()))
+    }
+
+    /// Creates a new `Frame` with the same identifier and CAN FD flags as this frame, but with the
+    /// given data.
+    const fn derive(&self, data: Bytes) -> Self {
+        Self {
+            id: self.id,
+            data,
+            fd_flags: self.fd_flags,
+            requested_dlc: self.requested_dlc,
+            timestamp: self.timestamp,
+            channel: self.channel,
+            status: self.status,
+        }
+    }
+
+    /// Decodes the payload of this frame as an ISO-TP "Single Frame".
+    ///
+    /// The first byte of the data is the protocol control information (PCI) byte, where the upper
+    /// nibble indicates the frame type, which must be zero for a "Single Frame", and the lower
+    /// nibble is the length of the payload that follows.  This is the inverse of
+    /// [`as_isotp_frame`][Self::as_isotp_frame].
+    ///
+    /// For CAN FD frames with payloads larger than seven bytes, the lower nibble is instead zero, and
+    /// the length of the payload is held in the second byte.  This is supported as well.
+    ///
+    /// # Errors
+    ///
+    /// If this frame is not a well-formed ISO-TP "Single Frame", either because the frame type is
+    /// not a "Single Frame", the length is zero, the length is not encoded as described above, or
+    /// the length exceeds the data available in the frame, then `None` is returned.
+    pub fn decode_isotp_single(&self) -> Option<&[u8]> {
+        match isotp::parse_pci(&self.data)? {
+            (isotp::IsoTpPci::SingleFrame { len }, pci_len) => {
+                Some(&self.data[pci_len..pci_len + usize::from(len)])
+            }
+            _ => None,
+        }
+    }
+
+    /// Calculates the number of bits this frame occupies on the bus as a classic CAN frame.
+    ///
+    /// This covers the entire frame, from the start-of-frame bit through to the interframe space,
+    /// and includes the worst-case number of stuff bits: after every five consecutive bits of the
+    /// same value, a bit of the opposite value is inserted, which in the worst case adds one bit for
+    /// every four bits from the start-of-frame bit through to the end of the CRC sequence.  For
+    /// example, a standard data frame with eight bytes of data is at most 135 bits long, and an
+    /// extended data frame with eight bytes of data is at most 160 bits long.
+    ///
+    /// Remote frames carry no data field.  Data frames with more than eight bytes of data are
+    /// counted as having eight bytes of data.  CAN FD frames are counted as classic CAN frames: see
+    /// [`transmit_time_fd`][Self::transmit_time_fd] for CAN FD frames.
+    pub fn bit_length(&self) -> u32 {
+        let stuffed_bits = match self.id {
+            Id::Standard(_) => CLASSIC_STUFFED_BITS_STANDARD,
+            Id::Extended(_) => CLASSIC_STUFFED_BITS_EXTENDED,
+        };
+
+        let data_bits = if self.is_remote_frame() {
+            0
+        } else {
+            8 * self.data.len().min(CLASSIC_MAX_DATA_LEN) as u32
+        };
+
+        let stuffable_bits = stuffed_bits + data_bits;
+        let stuff_bits = (stuffable_bits - 1) / 4;
+
+        stuffable_bits + stuff_bits + CLASSIC_TRAILER_BITS
+    }
+
+    /// Calculates the worst-case time it takes to transmit this frame as a classic CAN frame.
+    ///
+    /// See [`bit_length`][Self::bit_length] for how the length of the frame is calculated.
+    ///
+    /// # Panics
+    ///
+    /// If `bitrate` is zero, this method will panic.
+    pub fn transmission_time(&self, bitrate: u32) -> Duration {
+        bits_to_duration(u64::from(self.bit_length()), bitrate)
+    }
+
+    /// Calculates the CRC of this frame as a classic CAN frame.
+    ///
+    /// The CRC is calculated with the CAN CRC-15 polynomial (0x4599) over the unstuffed bits of the
+    /// frame, from the start-of-frame bit through to the end of the data field, as they are
+    /// transmitted on the bus.  This includes the substitute remote request and identifier
+    /// extension bits for extended identifiers, the remote transmission request bit, the reserved
+    /// bits, and the DLC.
+    ///
+    /// Remote frames carry no data field, and use the requested DLC.  Data frames with more than
+    /// eight bytes of data are counted as having only their first eight bytes of data.  CAN FD
+    /// frames use a different CRC, and so are treated as classic CAN frames here.
+    pub fn crc15(&self) -> u16 {
+        let rtr = u64::from(self.is_remote_frame());
+
+        // The start-of-frame bit is dominant, and so is always zero.
+        let mut crc = crc15_update(0, 0, 1);
+        crc = match self.id {
+            // Identifier, RTR, IDE (dominant), and r0 (dominant).
+            Id::Standard(id) => crc15_update(crc, u64::from(id.as_raw()) << 3 | rtr << 2, 14),
+            // Base identifier, SRR (recessive), IDE (recessive), identifier extension, RTR, r1
+            // (dominant), and r0 (dominant).
+            Id::Extended(id) => {
+                let raw = u64::from(id.as_raw());
+                let bits = (raw >> 18) << 23 | 1 << 22 | 1 << 21 | (raw & 0x3FFFF) << 3 | rtr << 2;
+                crc15_update(crc, bits, 34)
+            }
+        };
+
+        let data = if self.is_remote_frame() {
+            &[][..]
+        } else {
+            &self.data[..self.data.len().min(CLASSIC_MAX_DATA_LEN)]
+        };
+        let dlc = if self.is_remote_frame() {
+            self.requested_dlc
+        } else {
+            data.len() as u8
+        };
+
+        crc = crc15_update(crc, u64::from(dlc), 4);
+        data.iter()
+            .fold(crc, |crc, byte| crc15_update(crc, u64::from(*byte), 8))
+    }
+
+    /// Calculates a hash of the logical content of this frame.
+    ///
+    /// The hash is the 64-bit FNV-1a hash of the identifier, in the raw format used by SocketCAN
+    /// (see [`Id::to_raw_with_flags`]) as four big-endian bytes, followed by the data.  As it does
+    /// not depend on the platform or the process, it is stable across runs and machines, and can be
+    /// used for deduplicating frames across capture logs.
+    ///
+    /// Capture metadata, such as the timestamp, channel, and status, is not hashed, and neither are
+    /// the CAN FD flags or the requested DLC of remote frames, so frames that are equal always have
+    /// the same hash.
+    pub fn content_hash(&self) -> u64 {
+        let id = self.id.to_raw_with_flags().to_be_bytes();
+        id.iter()
+            .chain(self.data.iter())
+            .fold(FNV1A_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV1A_PRIME)
+            })
+    }
+
+    /// Calculates the time it takes to transmit this frame as a CAN FD frame.
+    ///
+    /// A CAN FD frame is transmitted in two phases.  The arbitration phase covers the start of the
+    /// frame up to the bit rate switch (BRS) bit, as well as the end of the frame from the CRC
+    /// delimiter onwards, and is always transmitted at `arb_bitrate`.  The data phase covers
+    /// everything in between -- the DLC, the data itself, and the CRC -- and is transmitted at
+    /// `data_bitrate` if the bit rate switch flag is set, or `arb_bitrate` otherwise.
+    ///
+    /// The frame is timed as a CAN FD frame regardless of whether or not it is marked as one.
+    ///
+    /// The data is padded to the next valid CAN FD data length, as it would be on the wire.  The bit
+    /// counts used are nominal: the fixed stuff bits in the CRC field are accounted for, but dynamic
+    /// stuff bits, which depend on the actual contents of the frame, are not.
+    ///
+    /// # Panics
+    ///
+    /// If either `arb_bitrate` or `data_bitrate` are zero, this method will panic.
+    pub fn transmit_time_fd(&self, arb_bitrate: u32, data_bitrate: u32) -> Duration {
+        let arb_bits = match self.id {
+            Id::Standard(_) => FD_ARB_PHASE_BITS_STANDARD,
+            Id::Extended(_) => FD_ARB_PHASE_BITS_EXTENDED,
+        } + FD_ARB_PHASE_TRAILER_BITS;
+
+        let data_len = fd_padded_len(self.data.len()) as u64;
+        let data_bits = if data_len > 16 {
+            FD_DATA_PHASE_BITS_CRC21
+        } else {
+            FD_DATA_PHASE_BITS_CRC17
+        } + (data_len * 8);
+
+        let data_bitrate = if self.bit_rate_switch() {
+            data_bitrate
+        } else {
+            arb_bitrate
+        };
+
+        bits_to_duration(arb_bits, arb_bitrate) + bits_to_duration(data_bits, data_bitrate)
+    }
+}
+
+impl Filter {
+    /// Checks if the given frame matches the filter.
+    ///
+    /// Only the identifier of the frame, including its flags, is considered.  This is equivalent
+    /// to [`Frame::matches`].
+    pub const fn matches_frame(&self, frame: &Frame) -> bool {
+        self.matches(frame.id)
+    }
+}
+
+impl fmt::Debug for Frame {
+    /// Formats the frame with its identifier and data in hexadecimal.
+    ///
+    /// The identifier is followed by the frame type, which is prefixed with `EXTENDED` for extended
+    /// identifiers, and then the data as space-separated bytes, such as
+    /// `Frame { id: 0x7E8, flags: DATA, data: [02 41 0C] }`.  CAN FD flags, the requested DLC of
+    /// remote frames, the timestamp, the channel, and the status are only included when present.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frame_type = if self.is_error_frame() {
+            "ERROR"
+        } else if self.is_remote_frame() {
+            "REMOTE"
+        } else {
+            "DATA"
+        };
+        let addressing = match self.id {
+            Id::Standard(_) => "",
+            Id::Extended(_) => "EXTENDED|",
+        };
+
+        let mut debug = f.debug_struct("Frame");
+        debug
+            .field("id", &format_args!("{:#X}", self.id.as_raw()))
+            .field("flags", &format_args!("{}{}", addressing, frame_type))
+            .field("data", &HexBytes(&self.data));
+
+        if !self.fd_flags.is_empty() {
+            debug.field("fd_flags", &self.fd_flags);
+        }
+        if let Some(dlc) = self.requested_dlc() {
+            debug.field("requested_dlc", &dlc);
+        }
+        if let Some(timestamp) = self.timestamp {
+            debug.field("timestamp", &timestamp);
+        }
+        if let Some(channel) = self.channel {
+            debug.field("channel", &channel.index());
+        }
+        if !self.status.is_empty() {
+            debug.field("status", &self.status);
+        }
+
+        debug.finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for Frame {
+    /// Formats the identifier of the frame, including its flags, and its data in hexadecimal, such
+    /// as `Frame { id: 0x7E8, data: [02, 41, 0C] }`.
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "Frame {{ id: {}, data: {=[u8]:02X} }}",
+            self.id,
+            &self.data[..]
+        );
+    }
+}
+
+// Formats bytes as space-separated, two-digit hexadecimal values.
+struct HexBytes<'a>(&'a [u8]);
+
+impl fmt::Debug for HexBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{:02X}", byte)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl PartialEq for Frame {
+    /// Compares the logical content of two frames, ignoring capture metadata.
+    ///
+    /// See [`Frame`] for details on what is compared, and [`eq_exact`][Frame::eq_exact] for
+    /// comparing capture metadata as well.
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.data == other.data
+            && self.fd_flags == other.fd_flags
+            && self.requested_dlc == other.requested_dlc
+    }
+}
+
+/// A CAN channel.
+///
+/// Identifies a specific bus, or interface, in systems with multiple buses, such as `can0` and
+/// `can1` on Linux.  How channels map to actual interfaces is up to the application.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Channel(u8);
+
+impl Channel {
+    /// Creates a `Channel` from the given index.
+    pub const fn new(index: u8) -> Self {
+        Self(index)
+    }
+
+    /// Gets the index of this channel.
+    pub const fn index(&self) -> u8 {
+        self.0
+    }
+}
+
+/// Error returned when the data of a frame is too large for the type of frame being created.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FrameLengthError {
+    data_len: usize,
+    max_len: usize,
+}
+
+impl FrameLengthError {
+    pub(crate) const fn new(data_len: usize, max_len: usize) -> Self {
+        Self { data_len, max_len }
+    }
+
+    /// Gets the length of the data that was rejected.
+    pub const fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Gets the maximum data length allowed for the type of frame being created.
+    pub const fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+impl fmt::Display for FrameLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "data length of {} bytes exceeds the maximum of {} bytes",
+            self.data_len, self.max_len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for FrameLengthError {}
+
+/// Gets the length that a payload of the given length is padded to when sent in a CAN FD frame.
+///
+/// Lengths larger than the maximum CAN FD payload size are returned as-is.
+const fn fd_padded_len(len: usize) -> usize {
+    if len > FD_MAX_DATA_LEN {
+        return len;
+    }
+
+    match len_to_fd_dlc(len as u8) {
+        Some(dlc) => match fd_dlc_to_len(dlc) {
+            Some(padded_len) => padded_len as usize,
+            None => len,
+        },
+        None => len,
+    }
+}
+
+fn bits_to_duration(bits: u64, bitrate: u32) -> Duration {
+    Duration::from_nanos(bits * 1_000_000_000 / u64::from(bitrate))
+}
+
+/// Updates a CAN CRC-15 with the lower `len` bits of `bits`, from the most significant bit to the
+/// least significant bit.
+const fn crc15_update(mut crc: u16, bits: u64, len: u32) -> u16 {
+    let mut i = len;
+    while i > 0 {
+        i -= 1;
+        let next = (bits >> i) as u16 & 1 ^ crc >> 14 & 1;
+        crc = crc << 1 & CRC15_MASK;
+        if next != 0 {
+            crc ^= CRC15_POLYNOMIAL;
+        }
+    }
+
+    crc
+}
+
+/// Gets the value of a single hexadecimal digit, in either case.
+const fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bytes::Bytes;
+
+    use crate::{
+        constants::{ErrorClass, FdFlags, FrameStatus, IdentifierFlags},
+        identifier::{ExtendedId, Filter, Id, StandardId},
+    };
+
+    use super::{Channel, Frame};
+
+    #[test]
+    fn transmit_time_fd_brs() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), true).unwrap();
+
+        // Arbitration phase: 17 bits up to and including BRS, plus 13 bits from the CRC delimiter
+        // through the interframe space, for 30 bits at 500 kbit/s, or 60us.
+        //
+        // Data phase: ESI (1) + DLC (4) + data (512) + stuff count (4) + CRC-21 (21) + fixed stuff
+        // bits (7), for 549 bits at 2 Mbit/s, or 274.5us.
+        let expected = Duration::from_nanos(60_000 + 274_500);
+        assert_eq!(expected, frame.transmit_time_fd(500_000, 2_000_000));
+    }
+
+    #[test]
+    fn transmit_time_fd_padding() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let short = Frame::new_fd(id, Bytes::from(vec![0xAA; 9]), true).unwrap();
+        let padded = Frame::new_fd(id, Bytes::from(vec![0xAA; 12]), true).unwrap();
+
+        assert_eq!(
+            padded.transmit_time_fd(500_000, 2_000_000),
+            short.transmit_time_fd(500_000, 2_000_000)
+        );
+    }
+
+    #[test]
+    fn classic_length_boundary() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = Frame::try_new_classic(id, Bytes::from_static(&[0; 8])).unwrap();
+        assert!(frame.is_valid_classic());
+
+        let err = Frame::try_new_classic(id, Bytes::from_static(&[0; 9]))
+            .err()
+            .unwrap();
+        assert_eq!(9, err.data_len());
+        assert_eq!(8, err.max_len());
+
+        let frame = Frame::from_static(id, &[0; 9]);
+        assert!(!frame.is_valid_classic());
+    }
+
+    #[test]
+    fn transmit_time_fd_no_brs() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), false).unwrap();
+
+        // Same bit counts as with BRS, but all 579 bits are sent at 500 kbit/s.
+        let expected = Duration::from_nanos(1_158_000);
+        assert_eq!(expected, frame.transmit_time_fd(500_000, 2_000_000));
+    }
+
+    #[test]
+    fn fd() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), true).unwrap();
+        assert!(frame.is_fd());
+        assert!(frame.bit_rate_switch());
+        assert!(!frame.error_state_indicator());
+        assert!(frame.is_valid_fd());
+        assert!(!frame.is_valid_classic());
+
+        let frame = frame.with_error_state_indicator(true).unwrap();
+        assert!(frame.error_state_indicator());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 9]), false).unwrap();
+        assert!(!frame.bit_rate_switch());
+        assert!(frame.is_valid_fd());
+
+        let err = Frame::new_fd(id, Bytes::from(vec![0xAA; 65]), true)
+            .err()
+            .unwrap();
+        assert_eq!(65, err.data_len());
+        assert_eq!(64, err.max_len());
+
+        let frame = Frame::from_static(id, &[0xAA; 8]);
+        assert!(!frame.is_fd());
+        assert!(!frame.bit_rate_switch());
+        assert!(frame.clone().with_error_state_indicator(true).is_none());
+        assert_eq!(Some(frame.clone()), frame.with_error_state_indicator(false));
+    }
+
+    #[test]
+    fn fd_padding() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 9]), true).unwrap();
+        assert_eq!(12, frame.len());
+        assert_eq!(&[0xAA; 9], &frame.data()[..9]);
+        assert_eq!(&[0x00; 3], &frame.data()[9..]);
+        assert_eq!(9, frame.dlc());
+        assert!(frame.is_valid_fd());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 63]), true).unwrap();
+        assert_eq!(64, frame.len());
+        assert_eq!(&[0xAA; 63], &frame.data()[..63]);
+        assert_eq!(0x00, frame.data()[63]);
+        assert_eq!(15, frame.dlc());
+        assert!(frame.is_valid_fd());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 7]), true).unwrap();
+        assert_eq!(7, frame.len());
+    }
+
+    #[test]
+    fn decode_isotp_single() {
+        let id = Id::Standard(StandardId::new(0x7E0).unwrap());
+
+        let frame = Frame::from_static(id, &[0x01, 0x0C]);
+        let encoded = frame.as_isotp_frame().unwrap();
+        assert_eq!(Some(&[0x01, 0x0C][..]), encoded.decode_isotp_single());
+
+        // Padding after the payload is ignored.
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC]);
+        assert_eq!(Some(&[0x01, 0x0C][..]), frame.decode_isotp_single());
+
+        // Not a single frame, a zero length, and a length longer than the available data.
+        let frame = Frame::from_static(id, &[0x10, 0x14, 0x49, 0x02, 0x01, 0x31, 0x44, 0x34]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[0x00, 0x01]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[0x03, 0x01, 0x0C]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[]);
+        assert_eq!(None, frame.decode_isotp_single());
+
+        // Lengths over seven bytes must use the escape sequence, and the escape sequence must only
+        // be used for lengths over seven bytes.
+        let frame = Frame::from_static(id, &[0x08, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[0x0F; 16]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[0x00, 0x07, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(None, frame.decode_isotp_single());
+        let frame = Frame::from_static(id, &[0x00, 0x08, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            Some(&[1, 2, 3, 4, 5, 6, 7, 8][..]),
+            frame.decode_isotp_single()
+        );
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        let cloned = frame.clone();
+        assert_eq!(frame, cloned);
+
+        let other = Frame::from_static(id, &[0x02, 0x01, 0x0D]);
+        assert_ne!(frame, other);
+
+        let fd = Frame::new_fd(id, Bytes::from_static(&[0x02, 0x01, 0x0C]), false).unwrap();
+        assert_ne!(frame, fd);
+    }
+
+    #[test]
+    fn remote() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::remote(id, 4).unwrap();
+        assert!(frame.is_remote_frame());
+        assert!(!frame.is_data_frame());
+        assert!(frame.data().is_empty());
+        assert_eq!(Some(4), frame.requested_dlc());
+        assert_eq!(0x123, frame.id().as_raw());
+
+        assert!(Frame::remote(id, 8).is_some());
+        assert!(Frame::remote(id, 9).is_none());
+
+        let frame = Frame::from_static(id, &[0x01, 0x02]);
+        assert_eq!(None, frame.requested_dlc());
+    }
+
+    #[test]
+    fn error() {
+        let class = ErrorClass::CONTROLLER | ErrorClass::BUS_OFF;
+        let frame = Frame::error(class, [0, 0x04, 0, 0, 0, 0, 0, 0]);
+        assert!(frame.is_error_frame());
+        assert!(!frame.is_data_frame());
+        assert_eq!(0x44, frame.id().as_raw());
+        assert_eq!(Some(class), frame.error_class());
+        assert_eq!(&[0, 0x04, 0, 0, 0, 0, 0, 0], frame.data());
+
+        let id = Id::Standard(StandardId::new(0x44).unwrap());
+        let frame = Frame::from_static(id, &[]);
+        assert_eq!(None, frame.error_class());
+    }
+
+    #[test]
+    fn dlc_and_len() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = Frame::from_static(id, &[0x01, 0x02, 0x03]);
+        assert_eq!(3, frame.dlc());
+        assert_eq!(3, frame.len());
+        assert!(!frame.is_empty());
+
+        let frame = Frame::new(id, Bytes::from(vec![0xAA; 20]));
+        assert_eq!(8, frame.dlc());
+        assert_eq!(20, frame.len());
+
+        let frame = Frame::remote(id, 6).unwrap();
+        assert_eq!(6, frame.dlc());
+        assert_eq!(0, frame.len());
+        assert!(frame.is_empty());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 9]), false).unwrap();
+        assert_eq!(9, frame.dlc());
+        assert_eq!(12, frame.len());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 33]), false).unwrap();
+        assert_eq!(14, frame.dlc());
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), false).unwrap();
+        assert_eq!(15, frame.dlc());
+    }
+
+    #[test]
+    fn padded() {
+        let id = Id::Standard(StandardId::new(0x7E0).unwrap());
+
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]).padded(0xCC);
+        assert_eq!(
+            &[0x02, 0x01, 0x0C, 0xCC, 0xCC, 0xCC, 0xCC, 0xCC],
+            frame.data()
+        );
+        assert_eq!(id, frame.id());
+
+        let frame = Frame::from_static(id, &[0xAA; 8]);
+        assert_eq!(frame, frame.padded(0x00));
+
+        let frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 9]), true).unwrap();
+        let padded = frame.padded(0x00);
+        assert_eq!(12, padded.len());
+        assert_eq!(&[0x00; 3], &padded.data()[9..]);
+        assert!(padded.is_fd());
+        assert!(padded.bit_rate_switch());
+
+        let frame = Frame::remote(id, 8).unwrap();
+        assert!(frame.padded(0x00).is_empty());
+    }
+
+    #[test]
+    fn into_data() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let data = Bytes::from(vec![0x01, 0x02, 0x03]);
+        let ptr = data.as_ptr();
+
+        let frame = Frame::new(id, data);
+        let data = frame.into_data();
+        assert_eq!(ptr, data.as_ptr());
+        assert_eq!(&[0x01, 0x02, 0x03], &data[..]);
+    }
+
+    #[test]
+    fn fd_flags() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = Frame::from_static(id, &[0x01]);
+        assert_eq!(FdFlags::empty(), frame.fd_flags());
+
+        let frame = Frame::new_fd(id, Bytes::from_static(&[0x01]), false).unwrap();
+        assert_eq!(FdFlags::FDF, frame.fd_flags());
+
+        let frame = Frame::new_fd(id, Bytes::from_static(&[0x01]), true)
+            .unwrap()
+            .with_error_state_indicator(true)
+            .unwrap();
+        assert_eq!(FdFlags::all(), frame.fd_flags());
+
+        let frame = frame.with_error_state_indicator(false).unwrap();
+        assert_eq!(FdFlags::FDF | FdFlags::BRS, frame.fd_flags());
+    }
+
+    #[cfg(feature = "socketcan-compat")]
+    #[test]
+    fn socketcan_round_trip() {
+        use socketcan::{EmbeddedFrame as _, Frame as _};
+
+        use super::SocketCanFrameError;
+        use crate::identifier::ExtendedId;
+
+        let id = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        let socketcan_frame = socketcan::CanFrame::try_from(frame.clone()).unwrap();
+        assert!(socketcan_frame.is_extended());
+        assert_eq!(0x18DAF110, socketcan_frame.raw_id());
+        assert_eq!(frame, Frame::from(socketcan_frame));
+
+        let low_extended = Id::Extended(ExtendedId::new(0x123).unwrap());
+        let frame = Frame::from_static(low_extended, &[]);
+        let socketcan_frame = socketcan::CanFrame::try_from(frame.clone()).unwrap();
+        assert!(socketcan_frame.is_extended());
+        assert_eq!(frame, Frame::from(socketcan_frame));
+
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::remote(id, 3).unwrap();
+        let socketcan_frame = socketcan::CanFrame::try_from(frame.clone()).unwrap();
+        assert!(socketcan_frame.is_remote_frame());
+        assert_eq!(3, socketcan_frame.dlc());
+        assert_eq!(frame, Frame::from(socketcan_frame));
+
+        let frame = Frame::error(ErrorClass::BUS_OFF, [0; 8]);
+        let socketcan_frame = socketcan::CanFrame::try_from(frame.clone()).unwrap();
+        assert!(socketcan_frame.is_error_frame());
+        assert_eq!(frame, Frame::from(socketcan_frame));
+
+        let frame = Frame::new(id, Bytes::from(vec![0xAA; 9]));
+        assert!(matches!(
+            socketcan::CanFrame::try_from(frame),
+            Err(SocketCanFrameError::TooMuchData(_))
+        ));
+
+        let frame = Frame::new_fd(id, Bytes::from_static(&[0x01]), false).unwrap();
+        assert_eq!(
+            Err(SocketCanFrameError::FdNotSupported),
+            socketcan::CanFrame::try_from(frame.clone()).map(|_| ())
+        );
+
+        let socketcan_frame = socketcan::CanFdFrame::try_from(frame.clone()).unwrap();
+        assert_eq!(frame, Frame::from(socketcan_frame));
+    }
+
+    #[cfg(feature = "embedded-can-compat")]
+    #[test]
+    fn embedded_can_frame() {
+        use embedded_can::Frame as _;
+
+        let id = embedded_can::StandardId::new(0x7DF).unwrap();
+        let frame = <Frame as embedded_can::Frame>::new(id, &[0x02, 0x01, 0x0C]).unwrap();
+        assert_eq!(Id::Standard(StandardId::new(0x7DF).unwrap()), frame.id());
+        assert_eq!(
+            embedded_can::Id::Standard(id),
+            embedded_can::Frame::id(&frame)
+        );
+        assert!(!frame.is_extended());
+        assert!(embedded_can::Frame::is_data_frame(&frame));
+        assert_eq!(3, embedded_can::Frame::dlc(&frame));
+        assert_eq!(&[0x02, 0x01, 0x0C], embedded_can::Frame::data(&frame));
+        assert!(<Frame as embedded_can::Frame>::new(id, &[0; 9]).is_err());
+
+        let id = embedded_can::ExtendedId::new(0x18DB33F1).unwrap();
+        let frame = Frame::new_remote(id, 4).unwrap();
+        assert!(frame.is_extended());
+        assert!(embedded_can::Frame::is_remote_frame(&frame));
+        assert_eq!(4, embedded_can::Frame::dlc(&frame));
+        assert!(embedded_can::Frame::data(&frame).is_empty());
+        assert!(Frame::new_remote(id, 9).is_err());
+    }
+
+    #[test]
+    fn from_slice() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let frame = {
+            let data = vec![0x01, 0x02, 0x03];
+            Frame::try_from_slice(id, &data).unwrap()
+        };
+        assert_eq!(&[0x01, 0x02, 0x03], frame.data());
+
+        let err = Frame::try_from_slice(id, &[0xAA; 9]).unwrap_err();
+        assert_eq!(9, err.data_len());
+        assert_eq!(8, err.max_len());
+
+        let frame = Frame::from_slice(id, &[0xAA; 20]);
+        assert_eq!(20, frame.len());
+
+        let frame = {
+            let data = vec![0xAA; 64];
+            Frame::try_from_slice_fd(id, &data, true).unwrap()
+        };
+        assert_eq!(&[0xAA; 64], frame.data());
+        assert!(frame.is_fd());
+        assert!(frame.bit_rate_switch());
+
+        let frame = Frame::try_from_slice_fd(id, &[0xAA; 9], false).unwrap();
+        assert_eq!(12, frame.len());
+        assert!(!frame.bit_rate_switch());
+
+        let err = Frame::try_from_slice_fd(id, &[0xAA; 65], true).unwrap_err();
+        assert_eq!(65, err.data_len());
+        assert_eq!(64, err.max_len());
+    }
+
+    #[test]
+    fn timestamp() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x41, 0x0C]);
+        assert_eq!(None, frame.timestamp());
+
+        let timestamp = Duration::from_micros(1_609_459_200_000_123);
+        let frame = frame.with_timestamp(timestamp);
+        assert_eq!(Some(timestamp), frame.timestamp());
+        assert_eq!(Some(timestamp), frame.padded(0xCC).timestamp());
+    }
+
+    #[test]
+    fn channel() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x41, 0x0C]);
+        assert_eq!(None, frame.channel());
+
+        let tagged = frame.clone().with_channel(Channel::new(1));
+        assert_eq!(Some(Channel::new(1)), tagged.channel());
+        assert_eq!(1, tagged.channel().unwrap().index());
+
+        // The channel does not affect equality.
+        assert_eq!(frame, tagged);
+        assert_eq!(tagged, frame.with_channel(Channel::new(2)));
+    }
+
+    #[test]
+    fn matches() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x41, 0x0C]);
+        let remote = Frame::remote(id, 2).unwrap();
+        let other = Frame::from_static(Id::Standard(StandardId::new(0x7E9).unwrap()), &[]);
+
+        let filter = Filter::from_identity(id);
+        assert!(frame.matches(&filter));
+        assert!(filter.matches_frame(&frame));
+        assert!(!other.matches(&filter));
+        assert!(!filter.matches_frame(&other));
+
+        // Flags are part of the identifier, and so are matched as well.
+        assert!(!remote.matches(&filter));
+        assert!(remote.matches(&filter.allow_rtr_frames()));
+
+        let frames = [frame, remote, other];
+        let data_frames = frames
+            .iter()
+            .filter(|f| f.matches(&Filter::data_frames_only()));
+        assert_eq!(2, data_frames.count());
+    }
+
+    #[test]
+    fn crc15() {
+        // The standard check value for CRC-15/CAN, over the ASCII bytes "123456789".
+        let check = b"123456789"
+            .iter()
+            .fold(0, |crc, byte| super::crc15_update(crc, u64::from(*byte), 8));
+        assert_eq!(0x059E, check);
+
+        let sid = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let eid = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+
+        let frame = Frame::from_static(Id::Standard(StandardId::new(0x014).unwrap()), &[0x01]);
+        assert_eq!(0x7753, frame.crc15());
+
+        let frame = Frame::from_static(sid, &[0x02, 0x01, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(0x5649, frame.crc15());
+
+        let frame = Frame::remote(sid, 4).unwrap();
+        assert_eq!(0x3A42, frame.crc15());
+
+        let frame = Frame::from_static(eid, &[0x02, 0x01, 0x0C]);
+        assert_eq!(0x4E45, frame.crc15());
+
+        let frame = Frame::remote(eid, 8).unwrap();
+        assert_eq!(0x25D2, frame.crc15());
+    }
+
+    #[test]
+    fn with_data() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x12, 0x34])
+            .with_timestamp(Duration::from_millis(1))
+            .with_channel(Channel::new(1));
+
+        let replaced = frame.clone().with_data(Bytes::from_static(&[0x56]));
+        assert_eq!(id, replaced.id());
+        assert_eq!(&[0x56], replaced.data());
+        assert_eq!(frame.timestamp(), replaced.timestamp());
+        assert_eq!(frame.channel(), replaced.channel());
+
+        let swapped = frame.clone().map_data(|data| {
+            let mut swapped = data.to_vec();
+            swapped.reverse();
+            Bytes::from(swapped)
+        });
+        assert_eq!(id, swapped.id());
+        assert_eq!(&[0x34, 0x12], swapped.data());
+        assert!(swapped.eq_exact(&frame.with_data(Bytes::from_static(&[0x34, 0x12]))));
+
+        let fd = Frame::new_fd(id, Bytes::from_static(&[0x01]), true).unwrap();
+        let mapped = fd.map_data(|_| Bytes::from_static(&[0x02; 12]));
+        assert!(mapped.is_fd() && mapped.bit_rate_switch());
+        assert_eq!(12, mapped.len());
+    }
+
+    #[test]
+    fn status() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x41]);
+        assert_eq!(FrameStatus::empty(), frame.status());
+
+        let status = FrameStatus::TRUNCATED | FrameStatus::FIFO_OVERRUN;
+        let lossy = frame.clone().with_status(status);
+        assert_eq!(status, lossy.status());
+        assert_eq!(status, lossy.padded(0xCC).status());
+
+        // The status does not affect equality, but is compared by `eq_exact`.
+        assert_eq!(frame, lossy);
+        assert!(!frame.eq_exact(&lossy));
+        assert!(lossy.eq_exact(&frame.with_status(status)));
+
+        assert_eq!(
+            "Frame { id: 0x7E8, flags: DATA, data: [02 41], status: TRUNCATED | FIFO_OVERRUN }",
+            format!("{:?}", lossy)
+        );
+    }
+
+    #[test]
+    fn eq_exact() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x41, 0x0C]);
+        let first = frame.clone().with_timestamp(Duration::from_millis(1));
+        let second = frame.clone().with_timestamp(Duration::from_millis(2));
+
+        // Captures of the same message are equal, but not identical.
+        assert_eq!(first, second);
+        assert!(!first.eq_exact(&second));
+        assert!(first.eq_exact(&first.clone()));
+
+        let tagged = first.clone().with_channel(Channel::new(1));
+        assert_eq!(first, tagged);
+        assert!(!first.eq_exact(&tagged));
+        assert!(tagged.eq_exact(&first.with_channel(Channel::new(1))));
+
+        // Differences in content are never equal.
+        let other = Frame::from_static(id, &[0x02, 0x41, 0x0D]);
+        assert_ne!(frame, other);
+        assert!(!frame.eq_exact(&other));
+    }
+
+    #[test]
+    fn from_vec() {
+        let id = Id::Standard(StandardId::new(0x7E0).unwrap());
+        let data = vec![0x02, 0x01, 0x0C];
+        assert_eq!(
+            Frame::new(id, Bytes::from(data.clone())),
+            Frame::from_vec(id, data)
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            "Frame { id: 0x7E8, flags: DATA, data: [02 01 0C 00 00 00 00 00] }",
+            format!("{:?}", frame)
+        );
+
+        let frame = Frame::remote(id, 2).unwrap().with_channel(Channel::new(1));
+        assert_eq!(
+            "Frame { id: 0x7E8, flags: REMOTE, data: [], requested_dlc: 2, channel: 1 }",
+            format!("{:?}", frame)
+        );
+
+        let id = Id::Extended(crate::identifier::ExtendedId::new(0x18DAF110).unwrap());
+        let frame = Frame::new_fd(id, Bytes::from_static(&[0xAA]), true).unwrap();
+        assert_eq!(
+            "Frame { id: 0x18DAF110, flags: EXTENDED|DATA, data: [AA], fd_flags: BRS | FDF }",
+            format!("{:?}", frame)
+        );
+
+        let frame = Frame::error(ErrorClass::BUS_OFF, [0; 8]);
+        assert!(format!("{:?}", frame).contains("flags: ERROR"));
+    }
+
+    #[test]
+    fn bit_length() {
+        let sid = Id::Standard(StandardId::new(0x123).unwrap());
+        let eid = Id::Extended(crate::identifier::ExtendedId::new(0x18DAF110).unwrap());
+
+        // Standard frames: 47 fixed bits, plus data, plus stuff bits.
+        assert_eq!(135, Frame::from_static(sid, &[0; 8]).bit_length());
+        assert_eq!(55, Frame::from_static(sid, &[]).bit_length());
+        assert_eq!(65, Frame::from_static(sid, &[0]).bit_length());
+        assert_eq!(55, Frame::remote(sid, 8).unwrap().bit_length());
+        assert_eq!(135, Frame::new(sid, Bytes::from(vec![0; 20])).bit_length());
+
+        // Extended frames: 67 fixed bits, plus data, plus stuff bits.
+        assert_eq!(160, Frame::from_static(eid, &[0; 8]).bit_length());
+        assert_eq!(80, Frame::from_static(eid, &[]).bit_length());
+
+        let frame = Frame::from_static(sid, &[0; 8]);
+        assert_eq!(Duration::from_micros(270), frame.transmission_time(500_000));
+        assert_eq!(
+            Duration::from_micros(135),
+            frame.transmission_time(1_000_000)
+        );
+    }
+
+    #[test]
+    fn arbitration_key() {
+        let sid = |raw| Id::Standard(StandardId::new(raw).unwrap());
+        let eid = |raw| Id::Extended(ExtendedId::new(raw).unwrap());
+
+        let mut frames = vec![
+            Frame::from_static(eid(0x1FFF_FFFF), &[]),
+            Frame::remote(sid(0x123), 0).unwrap(),
+            Frame::from_static(eid(0x123 << 18), &[]),
+            Frame::from_static(sid(0x7FF), &[]),
+            Frame::from_static(eid(0x0000_0001), &[]),
+            Frame::from_static(sid(0x123), &[]),
+            Frame::from_static(sid(0x000), &[]),
+            Frame::from_static(eid((0x123 << 18) - 1), &[]),
+            Frame::from_static(eid(0x0002_0000), &[]),
+            Frame::from_static(eid(0x0001_FFFF), &[]),
+        ];
+        frames.sort_by_key(Frame::arbitration_key);
+
+        let expected = vec![
+            Frame::from_static(sid(0x000), &[]),
+            Frame::from_static(eid(0x0000_0001), &[]),
+            Frame::from_static(eid(0x0001_FFFF), &[]),
+            Frame::from_static(eid(0x0002_0000), &[]),
+            Frame::from_static(eid((0x123 << 18) - 1), &[]),
+            Frame::from_static(sid(0x123), &[]),
+            Frame::remote(sid(0x123), 0).unwrap(),
+            Frame::from_static(eid(0x123 << 18), &[]),
+            Frame::from_static(sid(0x7FF), &[]),
+            Frame::from_static(eid(0x1FFF_FFFF), &[]),
+        ];
+        assert_eq!(expected, frames);
+
+        for pair in frames.windows(2) {
+            assert!(pair[0].id().wins_arbitration_over(&pair[1].id()));
+        }
+    }
+
+    #[test]
+    fn payload_slice() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::from_static(id, &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+
+        let slice = frame.payload_slice(2..4).unwrap();
+        assert_eq!(&[0x03, 0x04][..], &slice[..]);
+        assert_eq!(frame.data()[2..].as_ptr(), slice.as_ptr());
+
+        assert_eq!(Some(frame.data()), frame.payload_slice(..).as_deref());
+        assert_eq!(Some(&[0x05, 0x06][..]), frame.payload_slice(4..).as_deref());
+        assert_eq!(Some(&[0x01][..]), frame.payload_slice(..=0).as_deref());
+        assert_eq!(Some(&[][..]), frame.payload_slice(6..).as_deref());
+
+        assert_eq!(None, frame.payload_slice(2..7));
+        assert_eq!(None, frame.payload_slice(7..));
+        assert_eq!(None, frame.payload_slice(..=6));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = frame.payload_slice(4..2);
+        assert_eq!(None, reversed);
+    }
+
+    #[test]
+    fn content_hash() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::from_static(id, &[0x01, 0x02, 0x03]);
+        let captured = Frame::from_static(id, &[0x01, 0x02, 0x03])
+            .with_timestamp(Duration::from_secs(1))
+            .with_channel(Channel::new(1))
+            .with_status(FrameStatus::TRUNCATED);
+        assert_eq!(frame.content_hash(), captured.content_hash());
+
+        // The hash is fixed, and does not depend on the process.
+        assert_eq!(
+            0x4D22_337F_9DCB_6C45,
+            Frame::from_static(id, &[]).content_hash()
+        );
+        assert_ne!(
+            frame.content_hash(),
+            Frame::from_static(id, &[0x01, 0x02, 0x04]).content_hash()
+        );
+        assert_ne!(
+            frame.content_hash(),
+            Frame::from_static(
+                Id::Extended(ExtendedId::new(0x123).unwrap()),
+                &[0x01, 0x02, 0x03]
+            )
+            .content_hash()
+        );
+    }
+
+    #[test]
+    fn set_frame_type() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let mut frame = Frame::from_static(id, &[0x01, 0x02, 0x03]);
+
+        frame.set_remote(true);
+        assert!(frame.is_remote_frame());
+        assert!(frame.is_empty());
+        assert_eq!(Some(3), frame.requested_dlc());
+        assert_eq!(Frame::remote(id, 3).unwrap(), frame);
+        assert_eq!(Ok(()), frame.validate(false));
+
+        frame.set_remote(true);
+        assert_eq!(Some(3), frame.requested_dlc());
+
+        frame.set_remote(false);
+        assert!(frame.is_data_frame());
+        assert_eq!(None, frame.requested_dlc());
+        assert_eq!(Frame::from_static(id, &[]), frame);
+
+        frame.set_error(true);
+        assert!(frame.is_error_frame());
+        frame.set_error(false);
+        assert!(frame.is_data_frame());
+    }
+
+    #[test]
+    fn set_remote_fd() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let mut frame = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), true).unwrap();
+        assert_eq!(15, frame.dlc());
+        frame.set_remote(true);
+        assert!(frame.is_remote_frame());
+        assert!(!frame.is_fd());
+        assert_eq!(FdFlags::empty(), frame.fd_flags());
+        assert_eq!(Some(8), frame.requested_dlc());
+        assert_eq!(Frame::remote(id, 8).unwrap(), frame);
+        assert_eq!(Ok(()), frame.validate(false));
+
+        let mut frame = Frame::new_fd(id, Bytes::from_static(&[0x01, 0x02]), false).unwrap();
+        frame.set_remote(true);
+        assert!(!frame.is_fd());
+        assert_eq!(Some(2), frame.requested_dlc());
+        assert_eq!(Ok(()), frame.validate(false));
+    }
+
+    #[test]
+    fn set_extended() {
+        let sid = Id::Standard(StandardId::new(0x123).unwrap());
+        let eid = Id::Extended(ExtendedId::new(0x123).unwrap());
+        let mut frame = Frame::from_static(sid, &[0x01]);
+        frame.set_remote(true);
+
+        assert!(frame.set_extended(true));
+        assert_eq!(eid.set_flags(IdentifierFlags::REMOTE), frame.id());
+        assert!(frame.is_remote_frame());
+
+        assert!(frame.set_extended(false));
+        assert_eq!(sid.set_flags(IdentifierFlags::REMOTE), frame.id());
+        assert!(!frame.id().flags().contains(IdentifierFlags::EXTENDED));
+
+        let eid = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let mut frame = Frame::from_static(eid, &[0x01]);
+        assert!(!frame.set_extended(false));
+        assert_eq!(eid, frame.id());
+        assert!(frame.set_extended(true));
+        assert_eq!(eid, frame.id());
+    }
+
+    #[test]
+    fn modify_data() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+
+        let mut frame = Frame::from_vec(id, vec![0x01, 0x02, 0x03]);
+        assert!(frame.modify_data(|data| data[1] = 0xFF));
+        assert_eq!(&[0x01, 0xFF, 0x03][..], frame.data());
+
+        let clone = frame.clone();
+        assert!(!frame.modify_data(|_| panic!("shared data should not be modified")));
+        assert_eq!(clone, frame);
+        drop(clone);
+        assert!(frame.modify_data(|data| data[0] = 0xAA));
+        assert_eq!(&[0xAA, 0xFF, 0x03][..], frame.data());
+
+        let mut frame = Frame::from_static(id, &[0x01, 0x02, 0x03]);
+        assert!(!frame.modify_data(|_| panic!("static data should not be modified")));
+        assert_eq!(&[0x01, 0x02, 0x03][..], frame.data());
+    }
+
+    #[test]
+    fn eq_ignoring_padding() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let short = Frame::from_static(id, &[0x02, 0x41, 0x0C]);
+        let padded = Frame::from_static(id, &[0x02, 0x41, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        assert_ne!(short, padded);
+        assert!(short.eq_ignoring_padding(&padded, 0x00));
+        assert!(padded.eq_ignoring_padding(&short, 0x00));
+        assert!(!short.eq_ignoring_padding(&padded, 0xCC));
+        assert!(short.eq_ignoring_padding(&short.padded(0xCC), 0xCC));
+
+        let other_data = Frame::from_static(id, &[0x02, 0x41, 0x0D, 0x00]);
+        assert!(!short.eq_ignoring_padding(&other_data, 0x00));
+
+        let other_id = Frame::from_static(
+            Id::Standard(StandardId::new(0x7E9).unwrap()),
+            &[0x02, 0x41, 0x0C],
+        );
+        assert!(!short.eq_ignoring_padding(&other_id, 0x00));
+    }
+
+    #[test]
+    fn to_fixed_bytes() {
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+
+        let (bytes, len) = frame.to_classic_bytes().unwrap();
+        assert_eq!([0x02, 0x01, 0x0C, 0, 0, 0, 0, 0], bytes);
+        assert_eq!(3, len);
+
+        let (bytes, len) = frame.to_fd_bytes().unwrap();
+        assert_eq!(&[0x02, 0x01, 0x0C], &bytes[..3]);
+        assert!(bytes[3..].iter().all(|b| *b == 0));
+        assert_eq!(3, len);
+
+        let fd = Frame::new_fd(id, Bytes::from(vec![0xAA; 64]), true).unwrap();
+        assert_eq!(None, fd.to_classic_bytes());
+        assert_eq!(Some(([0xAA; 64], 64)), fd.to_fd_bytes());
+
+        let remote = Frame::remote(id, 8).unwrap();
+        assert_eq!(Some(([0; 8], 0)), remote.to_classic_bytes());
+
+        let oversized = Frame::new(id, Bytes::from(vec![0; 65]));
+        assert_eq!(None, oversized.to_fd_bytes());
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<Frame>();
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use super::Frame;
+
+/// Layout selection for multiplexed messages.
+///
+/// Some messages are multiplexed, where a single identifier is used to carry several different
+/// payload layouts.  The first byte of the payload, referred to as the multiplexor, indicates which
+/// of the layouts is in use for any given frame.  This is a common pattern in CAN databases (DBC
+/// files), where a message defines a multiplexor signal, and each of the other signals is only
+/// present for a specific multiplexor value.
+///
+/// [`Multiplexer`] maps multiplexor values to a caller-defined layout, which could be anything from
+/// a simple enum to a full signal definition, allowing a decoder to branch on the layout that is
+/// active for a given frame.
+#[derive(Clone, Debug)]
+pub struct Multiplexer<L> {
+    layouts: Vec<(u8, L)>,
+}
+
+impl<L> Multiplexer<L> {
+    /// Creates an empty [`Multiplexer`].
+    pub const fn new() -> Self {
+        Self {
+            layouts: Vec::new(),
+        }
+    }
+
+    /// Updates this [`Multiplexer`] to select the given layout for the given multiplexor value.
+    ///
+    /// If a layout was already registered for the multiplexor value, it is replaced.
+    pub fn with_layout(mut self, multiplexor: u8, layout: L) -> Self {
+        match self
+            .layouts
+            .iter_mut()
+            .find(|(value, _)| *value == multiplexor)
+        {
+            Some((_, existing)) => *existing = layout,
+            None => self.layouts.push((multiplexor, layout)),
+        }
+        self
+    }
+
+    /// Gets the multiplexor value of the given frame.
+    ///
+    /// If the frame has no data, `None` is returned.
+    pub fn multiplexor(frame: &Frame) -> Option<u8> {
+        frame.data().first().copied()
+    }
+
+    /// Selects the layout that is active for the given frame.
+    ///
+    /// If the frame has no data, or no layout is registered for its multiplexor value, `None` is
+    /// returned.
+    pub fn select(&self, frame: &Frame) -> Option<&L> {
+        let multiplexor = Self::multiplexor(frame)?;
+        self.layouts
+            .iter()
+            .find(|(value, _)| *value == multiplexor)
+            .map(|(_, layout)| layout)
+    }
+}
+
+impl<L> Default for Multiplexer<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::identifier::{Id, StandardId};
+
+    use super::{Frame, Multiplexer};
+
+    #[derive(Debug, PartialEq)]
+    enum Layout {
+        Temperatures,
+        Pressures,
+    }
+
+    #[test]
+    fn select() {
+        let id = Id::Standard(StandardId::new(0x321).unwrap());
+        let multiplexer = Multiplexer::new()
+            .with_layout(0, Layout::Temperatures)
+            .with_layout(1, Layout::Pressures);
+
+        let temperatures = Frame::from_static(id, &[0x00, 0x12, 0x34]);
+        let pressures = Frame::from_static(id, &[0x01, 0x56, 0x78]);
+        let unknown = Frame::from_static(id, &[0x02, 0x9A, 0xBC]);
+        let empty = Frame::from_static(id, &[]);
+
+        assert_eq!(
+            Some(&Layout::Temperatures),
+            multiplexer.select(&temperatures)
+        );
+        assert_eq!(Some(&Layout::Pressures), multiplexer.select(&pressures));
+        assert_eq!(None, multiplexer.select(&unknown));
+        assert_eq!(None, multiplexer.select(&empty));
+    }
+}
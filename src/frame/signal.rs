@@ -0,0 +1,380 @@
+use core::fmt;
+
+use super::Frame;
+
+// Maximum length, in bits, of a signal.
+const SIGNAL_MAX_LEN: usize = 64;
+
+/// Bit numbering of a signal within a payload.
+///
+/// CAN databases (DBC files) describe signals by a start bit, a length, and a byte order.  Bits
+/// are numbered from the least significant bit of the first byte, such that bit 0 is the least
+/// significant bit of byte 0, bit 7 is the most significant bit of byte 0, bit 8 is the least
+/// significant bit of byte 1, and so on.  The byte order then determines how the remaining bits of
+/// the signal are laid out relative to the start bit.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endianness {
+    /// Little-endian, also referred to as Intel byte order.
+    ///
+    /// The start bit is the least significant bit of the signal, and the signal continues towards
+    /// higher bit numbers, running into the next byte after the most significant bit of a byte.
+    Little,
+
+    /// Big-endian, also referred to as Motorola byte order.
+    ///
+    /// The start bit is the most significant bit of the signal, and the signal continues towards
+    /// lower bit numbers, running into the most significant bit of the next byte after the least
+    /// significant bit of a byte.
+    Big,
+}
+
+/// An error that occurred while inserting a signal into a payload.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SignalError {
+    /// The length of the signal was zero or greater than 64, or the signal extended beyond the end
+    /// of the payload.
+    InvalidRange,
+
+    /// The value did not fit in the length of the signal.
+    ValueTooLarge,
+}
+
+impl fmt::Display for SignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRange => write!(f, "signal range is invalid for payload"),
+            Self::ValueTooLarge => write!(f, "value does not fit in signal length"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for SignalError {}
+
+impl Frame {
+    /// Extracts a signal of `len` bits from the payload of this frame.
+    ///
+    /// The signal starts at `start_bit` and is laid out according to `endianness`.  See
+    /// [`Endianness`] for details on how bits are numbered.  The signal is returned as an unsigned
+    /// integer, without any scaling or sign extension applied.
+    ///
+    /// # Errors
+    ///
+    /// If `len` is zero or greater than 64, or if any bit of the signal is beyond the end of the
+    /// payload, `None` is returned.
+    pub fn extract_bits(
+        &self,
+        start_bit: usize,
+        len: usize,
+        endianness: Endianness,
+    ) -> Option<u64> {
+        let data = self.data();
+        bit_positions(start_bit, len, endianness)?.try_fold(0u64, |value, pos| {
+            let byte = data.get(pos / 8)?;
+            Some(value << 1 | u64::from(byte >> (pos % 8) & 1))
+        })
+    }
+
+    /// Inserts a signal of `len` bits into a payload.
+    ///
+    /// The signal starts at `start_bit` and is laid out according to `endianness`.  See
+    /// [`Endianness`] for details on how bits are numbered.  Only the bits of the signal are
+    /// modified, and all other bits of the payload are left as-is.  This is the inverse of
+    /// [`extract_bits`][Self::extract_bits].
+    ///
+    /// This operates on a mutable byte slice, such as a `BytesMut`, so that a payload can be built
+    /// up from multiple signals before creating a frame from it.
+    ///
+    /// # Errors
+    ///
+    /// If `len` is zero or greater than 64, or if any bit of the signal is beyond the end of the
+    /// payload, [`SignalError::InvalidRange`] is returned.  If `value` does not fit in `len` bits,
+    /// [`SignalError::ValueTooLarge`] is returned.  The payload is not modified if an error is
+    /// returned.
+    pub fn insert_bits(
+        data: &mut [u8],
+        start_bit: usize,
+        len: usize,
+        value: u64,
+        endianness: Endianness,
+    ) -> Result<(), SignalError> {
+        let positions =
+            bit_positions(start_bit, len, endianness).ok_or(SignalError::InvalidRange)?;
+        if len < SIGNAL_MAX_LEN && value >> len != 0 {
+            return Err(SignalError::ValueTooLarge);
+        }
+
+        let bits = data.len() * 8;
+        if positions.clone().any(|pos| pos >= bits) {
+            return Err(SignalError::InvalidRange);
+        }
+
+        for (i, pos) in positions.enumerate() {
+            let bit = (value >> (len - 1 - i) & 1) as u8;
+            let byte = &mut data[pos / 8];
+            *byte = *byte & !(1 << (pos % 8)) | bit << (pos % 8);
+        }
+
+        Ok(())
+    }
+}
+
+// Generates bounds-checked methods for reading fixed-size integers from the payload of a frame.
+macro_rules! read_integers {
+    ($($name:ident: $ty:ty, $from_bytes:ident, $desc:literal;)*) => {
+        impl Frame {
+            $(
+                #[doc = concat!("Reads ", $desc, " from the payload at the given byte offset.")]
+                ///
+                /// # Errors
+                ///
+                /// If the value would extend beyond the end of the payload, `None` is returned.
+                pub fn $name(&self, offset: usize) -> Option<$ty> {
+                    self.read_array(offset).map(<$ty>::$from_bytes)
+                }
+            )*
+        }
+    };
+}
+
+read_integers! {
+    read_u8: u8, from_be_bytes, "an unsigned 8-bit integer";
+    read_i8: i8, from_be_bytes, "a signed 8-bit integer";
+    read_u16_be: u16, from_be_bytes, "a big-endian unsigned 16-bit integer";
+    read_u16_le: u16, from_le_bytes, "a little-endian unsigned 16-bit integer";
+    read_i16_be: i16, from_be_bytes, "a big-endian signed 16-bit integer";
+    read_i16_le: i16, from_le_bytes, "a little-endian signed 16-bit integer";
+    read_u32_be: u32, from_be_bytes, "a big-endian unsigned 32-bit integer";
+    read_u32_le: u32, from_le_bytes, "a little-endian unsigned 32-bit integer";
+    read_i32_be: i32, from_be_bytes, "a big-endian signed 32-bit integer";
+    read_i32_le: i32, from_le_bytes, "a little-endian signed 32-bit integer";
+    read_u64_be: u64, from_be_bytes, "a big-endian unsigned 64-bit integer";
+    read_u64_le: u64, from_le_bytes, "a little-endian unsigned 64-bit integer";
+    read_i64_be: i64, from_be_bytes, "a big-endian signed 64-bit integer";
+    read_i64_le: i64, from_le_bytes, "a little-endian signed 64-bit integer";
+}
+
+impl Frame {
+    // Reads `N` bytes from the payload at the given byte offset.
+    fn read_array<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        let end = offset.checked_add(N)?;
+        self.data().get(offset..end)?.try_into().ok()
+    }
+}
+
+/// Gets the positions of the bits of a signal, from the most significant bit to the least
+/// significant bit.
+///
+/// Positions are not bounds checked against the payload, and positions that would overflow instead
+/// saturate, which leaves them out of bounds for any payload.  If `len` is zero or greater than 64,
+/// `None` is returned.
+fn bit_positions(
+    start_bit: usize,
+    len: usize,
+    endianness: Endianness,
+) -> Option<impl Iterator<Item = usize> + Clone> {
+    if len == 0 || len > SIGNAL_MAX_LEN {
+        return None;
+    }
+
+    let msb = match endianness {
+        Endianness::Little => start_bit.saturating_add(len - 1),
+        Endianness::Big => start_bit,
+    };
+
+    let next = move |current: &usize| {
+        Some(match endianness {
+            Endianness::Little => current.saturating_sub(1),
+            // After the least significant bit of a byte, continue at the most significant bit of
+            // the next byte.
+            Endianness::Big if current % 8 == 0 => current.saturating_add(15),
+            Endianness::Big => current - 1,
+        })
+    };
+
+    Some(core::iter::successors(Some(msb), next).take(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use proptest::{arbitrary::any as arb_any, prop_assert_eq, proptest};
+
+    use crate::identifier::{Id, StandardId};
+
+    use super::{Endianness, Frame, SignalError};
+
+    fn frame(data: &'static [u8]) -> Frame {
+        Frame::from_static(Id::Standard(StandardId::new(0x7E8).unwrap()), data)
+    }
+
+    fn payload(data: &[u8]) -> Frame {
+        Frame::from_slice(Id::Standard(StandardId::new(0x7E8).unwrap()), data)
+    }
+
+    #[test]
+    fn extract_little_endian() {
+        let frame = frame(&[0x34, 0x12, 0xF0]);
+
+        assert_eq!(Some(0x1234), frame.extract_bits(0, 16, Endianness::Little));
+        assert_eq!(Some(0x23), frame.extract_bits(4, 8, Endianness::Little));
+        assert_eq!(Some(0x4), frame.extract_bits(0, 4, Endianness::Little));
+        assert_eq!(Some(0x1), frame.extract_bits(2, 1, Endianness::Little));
+        assert_eq!(Some(0xF), frame.extract_bits(20, 4, Endianness::Little));
+        assert_eq!(
+            Some(0xF01234),
+            frame.extract_bits(0, 24, Endianness::Little)
+        );
+    }
+
+    #[test]
+    fn extract_big_endian() {
+        let frame = frame(&[0x12, 0x34, 0xF0]);
+
+        assert_eq!(Some(0x1234), frame.extract_bits(7, 16, Endianness::Big));
+        assert_eq!(Some(0x23), frame.extract_bits(3, 8, Endianness::Big));
+        assert_eq!(Some(0x1), frame.extract_bits(7, 4, Endianness::Big));
+        assert_eq!(Some(0x1), frame.extract_bits(4, 1, Endianness::Big));
+        assert_eq!(Some(0xF), frame.extract_bits(23, 4, Endianness::Big));
+        assert_eq!(Some(0x1234F0), frame.extract_bits(7, 24, Endianness::Big));
+    }
+
+    #[test]
+    fn extract_full_width() {
+        let frame = frame(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+
+        assert_eq!(
+            Some(0x0807060504030201),
+            frame.extract_bits(0, 64, Endianness::Little)
+        );
+        assert_eq!(
+            Some(0x0102030405060708),
+            frame.extract_bits(7, 64, Endianness::Big)
+        );
+    }
+
+    #[test]
+    fn extract_out_of_range() {
+        let frame = frame(&[0x12, 0x34]);
+
+        assert_eq!(None, frame.extract_bits(0, 0, Endianness::Little));
+        assert_eq!(None, frame.extract_bits(0, 65, Endianness::Little));
+        assert_eq!(None, frame.extract_bits(8, 9, Endianness::Little));
+        assert_eq!(None, frame.extract_bits(16, 1, Endianness::Little));
+        assert_eq!(None, frame.extract_bits(usize::MAX, 2, Endianness::Little));
+        assert_eq!(None, frame.extract_bits(7, 17, Endianness::Big));
+        assert_eq!(None, frame.extract_bits(16, 1, Endianness::Big));
+    }
+
+    #[test]
+    fn insert() {
+        let mut data = [0u8; 3];
+        Frame::insert_bits(&mut data, 4, 8, 0x23, Endianness::Little).unwrap();
+        assert_eq!([0x30, 0x02, 0x00], data);
+
+        let mut data = [0xFFu8; 3];
+        Frame::insert_bits(&mut data, 3, 8, 0x23, Endianness::Big).unwrap();
+        assert_eq!([0xF2, 0x3F, 0xFF], data);
+
+        let mut data = BytesMut::zeroed(2);
+        Frame::insert_bits(&mut data, 7, 16, 0x1234, Endianness::Big).unwrap();
+        assert_eq!(&[0x12, 0x34], &data[..]);
+    }
+
+    #[test]
+    fn insert_invalid() {
+        let mut data = [0u8; 2];
+
+        assert_eq!(
+            Err(SignalError::InvalidRange),
+            Frame::insert_bits(&mut data, 0, 0, 0, Endianness::Little)
+        );
+        assert_eq!(
+            Err(SignalError::InvalidRange),
+            Frame::insert_bits(&mut data, 8, 9, 0, Endianness::Little)
+        );
+        assert_eq!(
+            Err(SignalError::InvalidRange),
+            Frame::insert_bits(&mut data, 7, 17, 0, Endianness::Big)
+        );
+        assert_eq!(
+            Err(SignalError::ValueTooLarge),
+            Frame::insert_bits(&mut data, 0, 4, 0x10, Endianness::Little)
+        );
+        assert_eq!([0, 0], data);
+    }
+
+    proptest! {
+        #[test]
+        fn insert_extract_round_trip(
+            initial in arb_any::<[u8; 8]>(),
+            start_bit in 0usize..64,
+            len in 1usize..=64,
+            value in arb_any::<u64>(),
+            big_endian in arb_any::<bool>(),
+        ) {
+            let endianness = if big_endian { Endianness::Big } else { Endianness::Little };
+            let value = if len == 64 { value } else { value & ((1 << len) - 1) };
+            let original = payload(&initial).extract_bits(start_bit, len, endianness);
+
+            let mut data = initial;
+            match Frame::insert_bits(&mut data, start_bit, len, value, endianness) {
+                Ok(()) => {
+                    let extracted = payload(&data).extract_bits(start_bit, len, endianness);
+                    prop_assert_eq!(Some(value), extracted);
+
+                    // Only the bits of the signal are modified, so writing back the original value
+                    // restores the original payload.
+                    let original = original.unwrap();
+                    Frame::insert_bits(&mut data, start_bit, len, original, endianness).unwrap();
+                    prop_assert_eq!(initial, data);
+                }
+                Err(e) => {
+                    prop_assert_eq!(SignalError::InvalidRange, e);
+                    prop_assert_eq!(None, original);
+                    prop_assert_eq!(initial, data);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn read_integers() {
+        let unsigned = frame(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+
+        assert_eq!(Some(0x01), unsigned.read_u8(0));
+        assert_eq!(Some(0x08), unsigned.read_u8(7));
+        assert_eq!(Some(0x0203), unsigned.read_u16_be(1));
+        assert_eq!(Some(0x0302), unsigned.read_u16_le(1));
+        assert_eq!(Some(0x05060708), unsigned.read_u32_be(4));
+        assert_eq!(Some(0x08070605), unsigned.read_u32_le(4));
+        assert_eq!(Some(0x0102030405060708), unsigned.read_u64_be(0));
+        assert_eq!(Some(0x0807060504030201), unsigned.read_u64_le(0));
+
+        let signed = frame(&[0xFF, 0xFE, 0x80, 0x00]);
+        assert_eq!(Some(-1), signed.read_i8(0));
+        assert_eq!(Some(-2), signed.read_i16_be(0));
+        assert_eq!(Some(-257), signed.read_i16_le(0));
+        assert_eq!(Some(-0x0001_8000), signed.read_i32_be(0));
+        assert_eq!(Some(0x0080_FEFF), signed.read_i32_le(0));
+    }
+
+    #[test]
+    fn read_integers_out_of_bounds() {
+        let frame = frame(&[0x01, 0x02, 0x03, 0x04]);
+
+        // Reads that end exactly at the end of the payload succeed.
+        assert_eq!(Some(0x04), frame.read_u8(3));
+        assert_eq!(Some(0x0304), frame.read_u16_be(2));
+        assert_eq!(Some(0x01020304), frame.read_u32_be(0));
+
+        // Reads that extend past the end of the payload fail.
+        assert_eq!(None, frame.read_u8(4));
+        assert_eq!(None, frame.read_u16_be(3));
+        assert_eq!(None, frame.read_u32_le(1));
+        assert_eq!(None, frame.read_u64_be(0));
+        assert_eq!(None, frame.read_i16_le(usize::MAX));
+        assert_eq!(None, frame.read_i64_be(usize::MAX - 4));
+    }
+}
@@ -0,0 +1,235 @@
+//! slcan (serial line CAN) support.
+//!
+//! slcan, also known as the LAWICEL protocol, is an ASCII protocol used by many USB-to-serial CAN
+//! adapters.  Each frame is sent as a single line, starting with a character that identifies the
+//! frame type, followed by the identifier, the DLC, and the data, all encoded as uppercase
+//! hexadecimal:
+//!
+//! - `t`: standard data frame, e.g. `t1232AABB`
+//! - `T`: extended data frame, e.g. `T123456782AABB`
+//! - `r`: standard remote frame, e.g. `r1232`
+//! - `R`: extended remote frame, e.g. `R123456782`
+//!
+//! Lines are terminated by a carriage return (`\r`).
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write as _};
+
+use crate::identifier::{ExtendedId, Id, StandardId};
+
+use super::{Frame, CLASSIC_MAX_DATA_LEN};
+
+// Number of hexadecimal digits used to encode a standard and extended identifier, respectively.
+const STANDARD_ID_DIGITS: usize = 3;
+const EXTENDED_ID_DIGITS: usize = 8;
+
+/// An error that occurred while decoding an slcan frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SlcanError {
+    /// The line did not start with a known frame type.
+    UnknownFrameType,
+
+    /// The line was too short, or too long, for the frame type and DLC.
+    InvalidLength,
+
+    /// The line contained a character that was not a valid hexadecimal digit.
+    InvalidHex,
+
+    /// The identifier was out of range for its addressing mode.
+    InvalidIdentifier,
+
+    /// The DLC was greater than eight.
+    InvalidDlc,
+}
+
+impl fmt::Display for SlcanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFrameType => write!(f, "unknown slcan frame type"),
+            Self::InvalidLength => write!(f, "invalid slcan frame length"),
+            Self::InvalidHex => write!(f, "invalid hexadecimal digit in slcan frame"),
+            Self::InvalidIdentifier => write!(f, "identifier out of range in slcan frame"),
+            Self::InvalidDlc => write!(f, "DLC greater than eight in slcan frame"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for SlcanError {}
+
+impl Frame {
+    /// Encodes this frame as an slcan frame.
+    ///
+    /// The trailing carriage return is not included.  As slcan only supports classic CAN frames,
+    /// any data beyond the first eight bytes is not encoded, and neither are CAN FD flags.  The
+    /// error flag cannot be represented, and so error frames are encoded as data frames.
+    pub fn to_slcan(&self) -> String {
+        let remote = self.is_remote_frame();
+        let (kind, id_digits) = match (self.id(), remote) {
+            (Id::Standard(_), false) => ('t', STANDARD_ID_DIGITS),
+            (Id::Standard(_), true) => ('r', STANDARD_ID_DIGITS),
+            (Id::Extended(_), false) => ('T', EXTENDED_ID_DIGITS),
+            (Id::Extended(_), true) => ('R', EXTENDED_ID_DIGITS),
+        };
+
+        let data = if remote {
+            &[][..]
+        } else {
+            &self.data()[..self.len().min(CLASSIC_MAX_DATA_LEN)]
+        };
+        let dlc = if remote { self.dlc() } else { data.len() as u8 };
+
+        let mut line = String::with_capacity(1 + id_digits + 1 + data.len() * 2);
+        line.push(kind);
+
+        // Writing to a `String` never fails.
+        let _ = write!(
+            line,
+            "{:0width$X}{:X}",
+            self.id().as_raw(),
+            dlc,
+            width = id_digits
+        );
+        for byte in data {
+            let _ = write!(line, "{:02X}", byte);
+        }
+
+        line
+    }
+
+    /// Decodes an slcan frame.
+    ///
+    /// A single trailing carriage return, if present, is ignored.  Hexadecimal digits are accepted
+    /// in either case.
+    ///
+    /// # Errors
+    ///
+    /// If the line is not a valid slcan data or remote frame, an error describing the problem is
+    /// returned.
+    pub fn from_slcan(line: &str) -> Result<Frame, SlcanError> {
+        let line = line.strip_suffix('\r').unwrap_or(line).as_bytes();
+        let (&kind, rest) = line.split_first().ok_or(SlcanError::UnknownFrameType)?;
+        let (extended, remote) = match kind {
+            b't' => (false, false),
+            b'r' => (false, true),
+            b'T' => (true, false),
+            b'R' => (true, true),
+            _ => return Err(SlcanError::UnknownFrameType),
+        };
+
+        let id_digits = if extended {
+            EXTENDED_ID_DIGITS
+        } else {
+            STANDARD_ID_DIGITS
+        };
+        if rest.len() < id_digits + 1 {
+            return Err(SlcanError::InvalidLength);
+        }
+
+        let (raw_id, rest) = rest.split_at(id_digits);
+        let raw_id = raw_id
+            .iter()
+            .try_fold(0u32, |acc, &c| Ok(acc << 4 | u32::from(hex_value(c)?)))?;
+        let id = if extended {
+            ExtendedId::new(raw_id).map(Id::Extended)
+        } else {
+            u16::try_from(raw_id)
+                .ok()
+                .and_then(StandardId::new)
+                .map(Id::Standard)
+        }
+        .ok_or(SlcanError::InvalidIdentifier)?;
+
+        let dlc = hex_value(rest[0])?;
+        if usize::from(dlc) > CLASSIC_MAX_DATA_LEN {
+            return Err(SlcanError::InvalidDlc);
+        }
+
+        let data = &rest[1..];
+        if remote {
+            if !data.is_empty() {
+                return Err(SlcanError::InvalidLength);
+            }
+
+            return Frame::remote(id, dlc).ok_or(SlcanError::InvalidDlc);
+        }
+
+        if data.len() != usize::from(dlc) * 2 {
+            return Err(SlcanError::InvalidLength);
+        }
+
+        let data = data
+            .chunks(2)
+            .map(|pair| Ok(hex_value(pair[0])? << 4 | hex_value(pair[1])?))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Frame::from_vec(id, data))
+    }
+}
+
+fn hex_value(c: u8) -> Result<u8, SlcanError> {
+    super::hex_value(c).ok_or(SlcanError::InvalidHex)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::identifier::{ExtendedId, Id, StandardId};
+
+    use super::{Frame, SlcanError};
+
+    #[test]
+    fn standard_data_frame() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::from_slice(id, &[0xAA, 0xBB, 0x01]);
+
+        assert_eq!("t1233AABB01", frame.to_slcan());
+        assert_eq!(Ok(frame.clone()), Frame::from_slcan("t1233AABB01\r"));
+        assert_eq!(Ok(frame), Frame::from_slcan("t1233aabb01"));
+
+        let empty = Frame::from_slice(id, &[]);
+        assert_eq!("t1230", empty.to_slcan());
+        assert_eq!(Ok(empty), Frame::from_slcan("t1230"));
+    }
+
+    #[test]
+    fn extended_remote_frame() {
+        let id = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let frame = Frame::remote(id, 4).unwrap();
+
+        assert_eq!("R18DAF1104", frame.to_slcan());
+        assert_eq!(Ok(frame), Frame::from_slcan("R18DAF1104\r"));
+    }
+
+    #[test]
+    fn extended_data_frame() {
+        let id = Id::Extended(ExtendedId::new(0x0000_0001).unwrap());
+        let frame = Frame::from_slice(id, &[0x01, 0x02]);
+
+        assert_eq!("T0000000120102", frame.to_slcan());
+        assert_eq!(Ok(frame), Frame::from_slcan("T0000000120102"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Err(SlcanError::UnknownFrameType), Frame::from_slcan(""));
+        assert_eq!(
+            Err(SlcanError::UnknownFrameType),
+            Frame::from_slcan("x1230")
+        );
+        assert_eq!(Err(SlcanError::InvalidLength), Frame::from_slcan("t12"));
+        assert_eq!(Err(SlcanError::InvalidLength), Frame::from_slcan("t1232AA"));
+        assert_eq!(Err(SlcanError::InvalidLength), Frame::from_slcan("r1232AA"));
+        assert_eq!(Err(SlcanError::InvalidHex), Frame::from_slcan("t12G0"));
+        assert_eq!(Err(SlcanError::InvalidHex), Frame::from_slcan("t1231GG"));
+        assert_eq!(
+            Err(SlcanError::InvalidIdentifier),
+            Frame::from_slcan("t8000")
+        );
+        assert_eq!(
+            Err(SlcanError::InvalidIdentifier),
+            Frame::from_slcan("T200000000")
+        );
+        assert_eq!(Err(SlcanError::InvalidDlc), Frame::from_slcan("t1239"));
+    }
+}
@@ -0,0 +1,199 @@
+use core::fmt;
+
+use crate::constants::IdentifierFlags;
+
+use super::{fd_padded_len, Frame, FrameLengthError, CLASSIC_MAX_DATA_LEN, FD_MAX_DATA_LEN};
+
+/// Error returned when a [`Frame`] is not well-formed for transmission.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrameValidationError {
+    /// The data of the frame is too large for the mode it is being validated for.
+    TooMuchData(FrameLengthError),
+
+    /// The data length of the frame is over eight bytes, but is not one of the lengths that a CAN FD
+    /// frame can hold.
+    InvalidFdLength(usize),
+
+    /// The identifier has both the remote and error flags set.
+    ConflictingFlags,
+
+    /// The frame is a remote frame, but carries data.
+    RemoteWithData,
+
+    /// The frame is a remote frame, but is being validated as, or is marked as, a CAN FD frame.
+    ///
+    /// CAN FD does not support remote frames.
+    RemoteFd,
+
+    /// The frame is marked as a CAN FD frame, but is being validated as a classic CAN frame.
+    FdFrameInClassicMode,
+}
+
+impl fmt::Display for FrameValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooMuchData(e) => e.fmt(f),
+            Self::InvalidFdLength(len) => {
+                write!(
+                    f,
+                    "data length of {} bytes is not a valid CAN FD length",
+                    len
+                )
+            }
+            Self::ConflictingFlags => write!(f, "both the remote and error flags are set"),
+            Self::RemoteWithData => write!(f, "remote frame carries data"),
+            Self::RemoteFd => write!(f, "CAN FD frames cannot be remote frames"),
+            Self::FdFrameInClassicMode => write!(f, "CAN FD frame cannot be sent as classic CAN"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for FrameValidationError {}
+
+impl Frame {
+    /// Checks that this frame is well-formed for transmission.
+    ///
+    /// If `fd` is `true`, the frame is validated as a CAN FD frame, and otherwise as a classic CAN
+    /// frame.  A frame is well-formed when:
+    ///
+    /// - its data fits in a single frame: eight bytes for classic CAN, and 64 bytes for CAN FD,
+    ///   where lengths over eight bytes must also be one of 12, 16, 20, 24, 32, 48, or 64 bytes
+    /// - its identifier does not have both the remote and error flags set
+    /// - if it is a remote frame, it carries no data, and is not a CAN FD frame
+    /// - if it is marked as a CAN FD frame, it is being validated as a CAN FD frame
+    ///
+    /// The identifier itself is always within range for its addressing mode, as [`Id`] cannot be
+    /// constructed otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If the frame is not well-formed, an error describing the first problem found is returned.
+    ///
+    /// [`Id`]: crate::identifier::Id
+    pub fn validate(&self, fd: bool) -> Result<(), FrameValidationError> {
+        if self
+            .id
+            .flags()
+            .contains(IdentifierFlags::REMOTE.union(IdentifierFlags::ERROR))
+        {
+            return Err(FrameValidationError::ConflictingFlags);
+        }
+
+        if self.is_remote_frame() {
+            if fd || self.is_fd() {
+                return Err(FrameValidationError::RemoteFd);
+            }
+            if !self.data.is_empty() {
+                return Err(FrameValidationError::RemoteWithData);
+            }
+        }
+
+        if !fd && self.is_fd() {
+            return Err(FrameValidationError::FdFrameInClassicMode);
+        }
+
+        let len = self.data.len();
+        let max_len = if fd {
+            FD_MAX_DATA_LEN
+        } else {
+            CLASSIC_MAX_DATA_LEN
+        };
+        if len > max_len {
+            return Err(FrameValidationError::TooMuchData(FrameLengthError::new(
+                len, max_len,
+            )));
+        }
+
+        if fd && fd_padded_len(len) != len {
+            return Err(FrameValidationError::InvalidFdLength(len));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        constants::IdentifierFlags,
+        identifier::{Id, StandardId},
+    };
+
+    use super::{Frame, FrameValidationError};
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    fn with_flags(flags: IdentifierFlags) -> Id {
+        Id::Standard(StandardId::with_flags(0x123, flags).unwrap())
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            Ok(()),
+            Frame::from_static(sid(0x123), &[1, 2, 3]).validate(false)
+        );
+        assert_eq!(
+            Ok(()),
+            Frame::from_static(sid(0x123), &[1, 2, 3]).validate(true)
+        );
+        assert_eq!(
+            Ok(()),
+            Frame::remote(sid(0x123), 4).unwrap().validate(false)
+        );
+
+        let fd = Frame::new_fd(sid(0x123), Bytes::from_static(&[0; 12]), true).unwrap();
+        assert_eq!(Ok(()), fd.validate(true));
+    }
+
+    #[test]
+    fn rejected() {
+        let remote_with_data = Frame::from_static(with_flags(IdentifierFlags::REMOTE), &[1]);
+        assert_eq!(
+            Err(FrameValidationError::RemoteWithData),
+            remote_with_data.validate(false)
+        );
+
+        let remote = Frame::remote(sid(0x123), 0).unwrap();
+        assert_eq!(Err(FrameValidationError::RemoteFd), remote.validate(true));
+
+        let conflicting = Frame::from_static(
+            with_flags(IdentifierFlags::REMOTE | IdentifierFlags::ERROR),
+            &[],
+        );
+        assert_eq!(
+            Err(FrameValidationError::ConflictingFlags),
+            conflicting.validate(false)
+        );
+
+        let oversized = Frame::from_static(sid(0x123), &[0; 9]);
+        let err = match oversized.validate(false) {
+            Err(FrameValidationError::TooMuchData(err)) => err,
+            other => panic!("unexpected result: {:?}", other),
+        };
+        assert_eq!(9, err.data_len());
+        assert_eq!(8, err.max_len());
+        assert_eq!(
+            Err(FrameValidationError::InvalidFdLength(9)),
+            oversized.validate(true)
+        );
+
+        let oversized = Frame::from_static(sid(0x123), &[0; 65]);
+        assert!(matches!(
+            oversized.validate(true),
+            Err(FrameValidationError::TooMuchData(_))
+        ));
+
+        let fd = Frame::new_fd(sid(0x123), Bytes::from_static(&[0; 8]), false).unwrap();
+        assert_eq!(
+            Err(FrameValidationError::FdFrameInClassicMode),
+            fd.validate(false)
+        );
+    }
+}
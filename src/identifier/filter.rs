@@ -1,11 +1,18 @@
-use std::ops::{Add, BitAnd, BitOr, BitXor, Sub};
+use core::{
+    cmp, fmt,
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub,
+        SubAssign,
+    },
+    str::FromStr,
+};
 
-use crate::constants::IdentifierFlags;
+use crate::constants::{j1939, IdentifierFlags, EFF_MASK, SFF_MASK};
 
 use super::{ExtendedId, Id, StandardId};
 
 /// Mask component of a filter.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Mask(u32);
 
 impl Mask {
@@ -15,12 +22,137 @@ impl Mask {
     /// Matches no bits in the identifier.
     pub const NONE: Mask = Self(0);
 
+    /// Matches the parameter group number (PGN) of a J1939 identifier.
+    ///
+    /// J1939 uses 29-bit identifiers, which are composed of a 3-bit priority, an 18-bit PGN, and an
+    /// 8-bit source address, from most significant to least significant.  This mask covers only the
+    /// PGN bits, ignoring the priority and source address, as well as the extended flag, so that
+    /// only extended identifiers are matched.
+    ///
+    /// For PGNs in the PDU1 format, where the PDU format byte is less than 240, the PDU specific
+    /// byte of the identifier is the destination address rather than part of the PGN.  See
+    /// [`Filter::j1939_pgn`] to build a filter that accounts for this.
+    pub const J1939_PGN: Mask = Self(IdentifierFlags::EXTENDED.bits() | j1939::PGN_MASK << 8);
+
     /// Creates a new [`Mask`].
     pub const fn new(mask: u32) -> Mask {
         Self(mask)
     }
+
+    /// Returns the mask as a raw integer.
+    pub const fn as_raw(&self) -> u32 {
+        self.0
+    }
+
+    /// Creates a [`Mask`] that ignores the low bits of an identifier.
+    ///
+    /// The mask ignores the lowest `n` bits, where `n` is the bit length of `delta`, i.e. every bit
+    /// at or below its highest set bit, and matches every bit above them.  When combined with a
+    /// base identifier, the resulting filter matches the aligned block of `2^n` identifiers that
+    /// contains `base`, wherever `base` lies within that block.
+    ///
+    /// The filter matches exactly the identifiers from `start` to `end`, inclusive, only when used
+    /// as `covering(start ^ end)`, as [`Filter::range`] does, and `start` and `end` are the first
+    /// and last identifiers of an aligned power-of-two block.  Otherwise, the filter does not match
+    /// that span exactly: with a base of 0x7E0, a delta of 0xA also matches 0x7EB to 0x7EF, while
+    /// with a base of 0x7E7, a delta of 1 ignores only bit 0, and so matches 0x7E6 and 0x7E7, but
+    /// not 0x7E8.
+    pub const fn covering(delta: u32) -> Mask {
+        let ignored_bits = u32::BITS - delta.leading_zeros();
+        match Self::ALL.0.checked_shl(ignored_bits) {
+            Some(mask) => Self(mask),
+            None => Self::NONE,
+        }
+    }
+}
+
+impl From<u32> for Mask {
+    fn from(mask: u32) -> Self {
+        Self::new(mask)
+    }
 }
 
+impl From<Mask> for u32 {
+    fn from(mask: Mask) -> Self {
+        mask.as_raw()
+    }
+}
+
+impl fmt::Display for Mask {
+    /// Formats the mask as eight uppercase hexadecimal digits, prefixed with `0x`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}", self.0)
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for Mask {
+    /// Formats the mask in the same way as [`Display`][fmt::Display].
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=u32:#010X}", self.0);
+    }
+}
+
+impl FromStr for Mask {
+    type Err = ParseMaskError;
+
+    /// Parses a mask from hexadecimal digits, in either case, optionally prefixed with `0x` or
+    /// `0X`.
+    ///
+    /// # Errors
+    ///
+    /// If there are no digits, more than eight digits, or any character that is not a hexadecimal
+    /// digit, an error is returned.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+
+        if digits.is_empty() {
+            return Err(ParseMaskError::Empty);
+        }
+        if !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseMaskError::InvalidDigit);
+        }
+        if digits.len() > 8 {
+            return Err(ParseMaskError::TooLong);
+        }
+
+        u32::from_str_radix(digits, 16)
+            .map(Self)
+            .map_err(|_| ParseMaskError::InvalidDigit)
+    }
+}
+
+/// Error returned when a [`Mask`] cannot be parsed from a string.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParseMaskError {
+    /// The string had no hexadecimal digits.
+    Empty,
+
+    /// The string contained a character that was not a hexadecimal digit.
+    InvalidDigit,
+
+    /// The string had more than eight hexadecimal digits.
+    TooLong,
+}
+
+impl fmt::Display for ParseMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "mask has no hexadecimal digits"),
+            Self::InvalidDigit => write!(f, "invalid hexadecimal digit in mask"),
+            Self::TooLong => write!(f, "mask has more than eight hexadecimal digits"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ParseMaskError {}
+
 impl BitAnd for Mask {
     type Output = Self;
 
@@ -61,6 +193,36 @@ impl Sub for Mask {
     }
 }
 
+impl BitAndAssign for Mask {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl BitOrAssign for Mask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitXorAssign for Mask {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+impl AddAssign for Mask {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Mask {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
 /// An identifier filter.
 ///
 /// Often times when communicating on a bus with many devices, it can be useful to filter which
@@ -126,17 +288,71 @@ impl Sub for Mask {
 /// masks.  These make it easier to construct filters based on functional need: match a single
 /// identifier, match error frames only, etc.
 ///
+/// ## Flag semantics
+///
+/// As the identifier flags are encoded in the upper bits of the 32-bit value, each flag is matched
+/// just like any other bit: if the flag bit is present in the mask, a message identifier only
+/// matches when its flag is set (or unset) in the same way as the flag in the filter identifier,
+/// and if the flag bit is absent from the mask, the flag is ignored entirely.  This means that a
+/// flag in the mask is never a way to _allow_ a particular frame type, only to require or forbid it.
+///
+/// The flags-based helper methods follow these semantics:
+///
+/// - [`any`][Filter::any] masks no flags, and so matches all frame types in either addressing mode
+/// - [`data_frames_only`][Filter::data_frames_only] requires both the remote and error flags to be
+///   unset
+/// - [`error_frames_only`][Filter::error_frames_only] requires the error flag to be set
+/// - the `allow_*` methods remove the given flag from the mask, such that the flag is ignored
+/// - the `disallow_*` methods add the given flag to the mask, and unset it in the filter
+///   identifier, such that the flag must be unset
+///
 /// [socketcan]: https://www.kernel.org/doc/Documentation/networking/can.txt
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Filter {
     id: Id,
     mask: Mask,
+    // The identifier and its flags, already masked, so that matching only needs to mask the
+    // identifier being matched.
+    masked_id: u32,
 }
 
 impl Filter {
     /// Creates a [`Filter`] based on the given identifier and mask.
     pub const fn new(id: Id, mask: Mask) -> Self {
-        Self { id, mask }
+        Self {
+            id,
+            mask,
+            masked_id: id.to_raw_with_flags() & mask.0,
+        }
+    }
+
+    /// Gets the identifier of this filter.
+    pub const fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Gets the mask of this filter.
+    pub const fn mask(&self) -> Mask {
+        self.mask
+    }
+
+    /// Creates a [`Filter`] from a raw identifier and mask, as used by SocketCAN.
+    ///
+    /// The identifier is decoded with [`Id::from_raw_with_flags`], so the `CAN_EFF_FLAG` bit
+    /// selects the addressing mode, and any address bits beyond the width of the identifier are
+    /// ignored.  The mask is used as-is.
+    ///
+    /// This is the inverse of [`to_raw`][Self::to_raw], and of converting a filter to a SocketCAN
+    /// filter.
+    pub const fn from_raw(can_id: u32, can_mask: u32) -> Self {
+        Self::new(Id::from_raw_with_flags(can_id), Mask(can_mask))
+    }
+
+    /// Gets the raw identifier and mask of this filter, as used by SocketCAN.
+    ///
+    /// The identifier includes its flags, as with [`Id::to_raw_with_flags`].
+    pub const fn to_raw(&self) -> (u32, u32) {
+        (self.id.to_raw_with_flags(), self.mask.0)
     }
 
     /// Creates a [`Filter`] that will only match the given [`Id`].
@@ -144,137 +360,625 @@ impl Filter {
     /// This only allows matching the identifier in its specific addressing mode.  In other words,
     /// if the identifier is 0x123 in standard addressing mode, an identifier of 0x123 in _extended_
     /// addressing mode will _not_ match.
+    ///
+    /// As the mask covers every bit, including the extended flag, the addressing mode is always
+    /// compared: a standard identity filter never matches an extended identifier that shares the
+    /// same lower bits, and vice versa.  The remote and error flags are likewise compared, so only
+    /// frames of the same type as the given identifier will match.
     pub const fn from_identity(id: Id) -> Self {
-        Self {
-            id,
-            mask: Mask::ALL,
-        }
+        Self::new(id, Mask::ALL)
+    }
+
+    /// Creates a [`Filter`] that will only match the given [`StandardId`].
+    ///
+    /// This is equivalent to [`from_identity`][Self::from_identity], but makes it explicit at the
+    /// call site that only the standard identifier is matched, and never an extended identifier
+    /// with the same value.
+    pub const fn standard_identity(sid: StandardId) -> Self {
+        Self::from_identity(Id::Standard(sid))
+    }
+
+    /// Creates a [`Filter`] that will only match the given [`ExtendedId`].
+    ///
+    /// This is equivalent to [`from_identity`][Self::from_identity], but makes it explicit at the
+    /// call site that only the extended identifier is matched, and never a standard identifier
+    /// with the same value.
+    pub const fn extended_identity(eid: ExtendedId) -> Self {
+        Self::from_identity(Id::Extended(eid))
     }
 
     /// Creates a [`Filter`] that will match any identifier between `start` and `end`, inclusive.
+    ///
+    /// A single identifier and mask can only exactly represent a range whose length is a power of
+    /// two, and whose start is aligned to that length, such as 0x7E0 to 0x7EF.  For any other
+    /// range, the filter matches a superset of the range: the smallest aligned power-of-two block
+    /// that contains both `start` and `end`.  For example, a range of 0x7E0 to 0x7EA matches 0x7E0
+    /// to 0x7EF.  See [`Mask::covering`] for more information.
     pub const fn range(start: Id, end: Id) -> Self {
-        let (id, delta_mask) = if start.as_raw() > end.as_raw() {
-            (end, start.as_raw() - end.as_raw())
+        let id = if start.as_raw() > end.as_raw() {
+            end
         } else {
-            (start, end.as_raw() - start.as_raw())
+            start
         };
 
-        Self {
-            id,
-            mask: Mask(Mask::ALL.0 - delta_mask),
+        Self::new(id, Mask::covering(start.as_raw() ^ end.as_raw()))
+    }
+
+    /// Creates a [`Filter`] that will match exactly the identifiers between `start` and `end`,
+    /// inclusive.
+    ///
+    /// # Errors
+    ///
+    /// If the range cannot be expressed exactly by a single filter, as described in
+    /// [`range`][Self::range], an error is returned, which holds the filter that would match a
+    /// superset of the range.  [`range_exact`][Self::range_exact] can be used to match such ranges
+    /// exactly with multiple filters.
+    pub const fn try_range(start: Id, end: Id) -> Result<Self, RangeNotExpressible> {
+        let filter = Self::range(start, end);
+
+        let (low, high) = if start.as_raw() > end.as_raw() {
+            (end.as_raw(), start.as_raw())
+        } else {
+            (start.as_raw(), end.as_raw())
+        };
+
+        let ignored_bits = !filter.mask.0;
+        if low & ignored_bits == 0 && high & ignored_bits == ignored_bits {
+            Ok(filter)
+        } else {
+            Err(RangeNotExpressible { superset: filter })
         }
     }
 
+    /// Creates a [`Filter`] that matches any J1939 identifier with the given parameter group number
+    /// (PGN).
+    ///
+    /// The priority and source address of the identifier are ignored.  For PGNs in the PDU1 format,
+    /// where the PDU format byte is less than 240, the PDU specific byte holds the destination
+    /// address rather than being part of the PGN, and so it is ignored as well, matching the PGN
+    /// regardless of destination.
+    ///
+    /// Only the lower 18 bits of `pgn` are used.
+    pub const fn j1939_pgn(pgn: u32) -> Self {
+        let pgn = pgn & j1939::PGN_MASK;
+        let (pgn, mask) = if (pgn >> 8) & 0xFF < j1939::PDU2_FORMAT_MIN as u32 {
+            (
+                pgn & !j1939::PDU_SPECIFIC_MASK,
+                Mask(Mask::J1939_PGN.0 & !(j1939::PDU_SPECIFIC_MASK << 8)),
+            )
+        } else {
+            (pgn, Mask::J1939_PGN)
+        };
+
+        let id = match ExtendedId::new(pgn << 8) {
+            Some(id) => id,
+            // An 18-bit PGN shifted past the 8-bit source address always fits within 29 bits.
+            None => unreachable!(),
+        };
+
+        Self::new(Id::Extended(id), mask)
+    }
+
     /// Creates a [`Filter`] that matches no identifiers.
     pub const fn none() -> Self {
         // Abuse the fact that, in practice, a CAN frame can/should never be a data frame, error
         // frame, and remote frame simultaneously. Callers could _technically_ construct an
         // identifier and do what we're doing here, but I'm comfortable saying: you brought this on
         // yourself. :P
-        Self {
-            id: Id::Extended(ExtendedId::MAX).set_flags(IdentifierFlags::all()),
-            mask: Mask::ALL,
-        }
+        Self::new(
+            Id::Extended(ExtendedId::MAX).set_flags(IdentifierFlags::all()),
+            Mask::ALL,
+        )
     }
 
     /// Creates a [`Filter`] that matches any identifiers.
+    ///
+    /// No flag bits are masked, so all frame types, in both addressing modes, are matched.
     pub const fn any() -> Self {
-        Self {
-            id: Id::Standard(StandardId::ZERO),
-            mask: Mask(0),
-        }
+        Self::new(Id::Standard(StandardId::ZERO), Mask(0))
     }
 
     /// Creates a [`Filter`] that matches only data frames.
+    ///
+    /// Both the remote and error flags are masked, and must be unset, so that neither remote frames
+    /// nor error frames are matched.  Frames in both addressing modes are matched.
     pub const fn data_frames_only() -> Self {
-        Self {
-            id: Id::Standard(StandardId::ZERO),
-            mask: Mask(IdentifierFlags::ERROR.bits()),
-        }
+        Self::new(
+            Id::Standard(StandardId::ZERO),
+            Mask(IdentifierFlags::ERROR.union(IdentifierFlags::REMOTE).bits()),
+        )
     }
 
     /// Creates a [`Filter`] that matches only error frames.
+    ///
+    /// The error flag is masked, and must be set.  Frames in both addressing modes are matched.
     pub const fn error_frames_only() -> Self {
-        Self {
-            id: Id::Standard(StandardId::ZERO.set_flags(IdentifierFlags::ERROR)),
-            mask: Mask(IdentifierFlags::ERROR.bits()),
-        }
+        Self::new(
+            Id::Standard(StandardId::ZERO.set_flags(IdentifierFlags::ERROR)),
+            Mask(IdentifierFlags::ERROR.bits()),
+        )
     }
 
     /// Updates this [`Filter`] to allow matching extended frames.
+    ///
+    /// The extended flag is removed from the mask, so frames match regardless of their addressing
+    /// mode.
     pub const fn allow_extended_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 | IdentifierFlags::EXTENDED.bits()),
-        }
+        Self::new(
+            self.id,
+            Mask(self.mask.0 & !IdentifierFlags::EXTENDED.bits()),
+        )
     }
 
     /// Updates this [`Filter`] to disallow matching extended frames.
+    ///
+    /// The extended flag is added to the mask, and must be unset, so only frames using standard
+    /// addressing will match.
+    ///
+    /// As an extended identifier always has the extended flag set, a filter based on an extended
+    /// identifier can never match a standard frame, and so disallowing extended frames for such a
+    /// filter results in a filter that matches no identifiers.
     pub const fn disallow_extended_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 & !IdentifierFlags::EXTENDED.bits()),
+        match self.id {
+            Id::Standard(_) => Self::new(
+                self.id,
+                Mask(self.mask.0 | IdentifierFlags::EXTENDED.bits()),
+            ),
+            Id::Extended(_) => Self::none(),
         }
     }
 
     /// Updates this [`Filter`] to allow matching remote frames.
+    ///
+    /// The remote flag is removed from the mask, so frames match regardless of whether or not they
+    /// are remote frames.
     pub const fn allow_rtr_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 | IdentifierFlags::REMOTE.bits()),
-        }
+        Self::new(self.id, Mask(self.mask.0 & !IdentifierFlags::REMOTE.bits()))
     }
 
     /// Updates this [`Filter`] to disallow matching remote frames.
+    ///
+    /// The remote flag is added to the mask, and must be unset, so remote frames will not match.
     pub const fn disallow_rtr_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 & !IdentifierFlags::REMOTE.bits()),
-        }
+        Self::new(
+            self.id
+                .set_flags(self.id.flags().difference(IdentifierFlags::REMOTE)),
+            Mask(self.mask.0 | IdentifierFlags::REMOTE.bits()),
+        )
     }
 
     /// Updates this [`Filter`] to allow matching error frames.
+    ///
+    /// The error flag is removed from the mask, so frames match regardless of whether or not they
+    /// are error frames.
     pub const fn allow_error_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 | IdentifierFlags::ERROR.bits()),
-        }
+        Self::new(self.id, Mask(self.mask.0 & !IdentifierFlags::ERROR.bits()))
     }
 
     /// Updates this [`Filter`] to disallow matching error frames.
+    ///
+    /// The error flag is added to the mask, and must be unset, so error frames will not match.
     pub const fn disallow_error_frames(self) -> Self {
-        Self {
-            id: self.id,
-            mask: Mask(self.mask.0 & !IdentifierFlags::ERROR.bits()),
-        }
+        Self::new(
+            self.id
+                .set_flags(self.id.flags().difference(IdentifierFlags::ERROR)),
+            Mask(self.mask.0 | IdentifierFlags::ERROR.bits()),
+        )
     }
 
     /// Checks if the given identifier matches the filter.
     pub const fn matches(&self, id: Id) -> bool {
+        id.to_raw_with_flags() & self.mask.0 == self.masked_id
+    }
+
+    /// Checks if this filter can never match any identifier.
+    ///
+    /// A frame is never a remote frame and an error frame at the same time, so a filter that
+    /// requires both the remote and error flags to be set can never match, such as
+    /// [`none`][Self::none].  This can also happen by accident when composing filters and masks.
+    ///
+    /// As the identifier of a filter is always a valid identifier for its addressing mode, the
+    /// identifier bits required by the mask never contradict each other, and so only the flags
+    /// need to be considered.
+    pub const fn is_empty(&self) -> bool {
+        let contradictory = IdentifierFlags::REMOTE.union(IdentifierFlags::ERROR).bits();
+        let required = self.id.flags().bits() & self.mask.0;
+
+        required & contradictory == contradictory
+    }
+
+    /// Gets the number of distinct identifiers that this filter matches.
+    ///
+    /// Standard and extended identifiers with the same value are distinct identifiers, so a filter
+    /// that ignores the addressing mode, such as [`any`][Self::any], matches all 2048 standard
+    /// identifiers as well as all 2^29 extended identifiers.  The remote and error flags indicate
+    /// the frame type rather than forming part of the identifier, and so do not affect the count,
+    /// other than a filter that [can never match][Self::is_empty] having a count of zero.
+    pub const fn match_count(&self) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let extended_flag = IdentifierFlags::EXTENDED.bits();
+        let required = (self.id.as_raw() | self.id.flags().bits()) & self.mask.0;
+        let mode_masked = self.mask.0 & extended_flag != 0;
+
+        // Standard identifiers can only match if the mask doesn't require the extended flag, and
+        // doesn't require any of the bits beyond the lower 11 bits to be set.
+        let standard = if (mode_masked && required & extended_flag != 0)
+            || required & EFF_MASK & !SFF_MASK != 0
+        {
+            0
+        } else {
+            1 << (SFF_MASK & !self.mask.0).count_ones()
+        };
+
+        let extended = if mode_masked && required & extended_flag == 0 {
+            0
+        } else {
+            1 << (EFF_MASK & !self.mask.0).count_ones()
+        };
+
+        standard + extended
+    }
+
+    /// Checks if this filter matches every identifier that `other` matches.
+    ///
+    /// This is the case when every bit that this filter requires is also required, in the same way,
+    /// by `other`, or when `other` [can never match][Self::is_empty] at all.  Equal filters subsume
+    /// each other.
+    ///
+    /// The check is conservative: it considers the raw bits of the filters, and so may return
+    /// `false` for filters that only differ in bits that can never be set for the identifiers that
+    /// `other` matches, such as the upper bits of a standard identifier.
+    pub const fn subsumes(&self, other: &Filter) -> bool {
+        if other.is_empty() {
+            return true;
+        }
+
         let self_id = self.id.as_raw() | self.id.flags().bits();
-        let other_id = id.as_raw() | id.flags().bits();
+        let other_id = other.id.as_raw() | other.id.flags().bits();
+
+        self.mask.0 & !other.mask.0 == 0 && (self_id ^ other_id) & self.mask.0 == 0
+    }
+
+    /// Gets the specificity of this filter.
+    ///
+    /// The specificity is the number of bits set in the mask, including the flag bits.  Each bit
+    /// in the mask constrains the identifiers that can match, so a filter with a higher specificity
+    /// generally matches fewer identifiers: [`from_identity`][Self::from_identity] has the highest
+    /// specificity, while [`any`][Self::any] has a specificity of zero.
+    pub const fn specificity(&self) -> u32 {
+        self.mask.0.count_ones()
+    }
+
+    /// Compares two filters by their specificity.
+    ///
+    /// Filters with a lower specificity are ordered first, so sorting with this comparator puts the
+    /// least specific filters first.  This is useful when only a limited number of filters can be
+    /// used, such as when programming hardware filter banks, to decide which filters to drop.
+    ///
+    /// `Filter` does not implement `Ord`, as ordering filters by specificity is not the only
+    /// sensible ordering, and filters with the same specificity are not equal.
+    ///
+    /// ```
+    /// use can::identifier::{Filter, Id, StandardId};
+    ///
+    /// let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+    /// let mut filters = vec![Filter::any(), Filter::from_identity(id), Filter::data_frames_only()];
+    ///
+    /// // Sort the most specific filters first.
+    /// filters.sort_by(|a, b| Filter::by_specificity(b, a));
+    /// assert_eq!(Filter::from_identity(id), filters[0]);
+    /// assert_eq!(Filter::any(), filters[2]);
+    /// ```
+    pub fn by_specificity(a: &Filter, b: &Filter) -> cmp::Ordering {
+        a.specificity().cmp(&b.specificity())
+    }
+}
 
-        other_id & self.mask.0 == self_id & self.mask.0
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for Filter {
+    /// Formats the identifier and mask of the filter, such as
+    /// `Filter { id: 0x7E8, mask: 0x000007FF }`.
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "Filter {{ id: {}, mask: {} }}", self.id, self.mask);
     }
 }
 
-#[cfg(feature = "socketcan-compat")]
-#[cfg_attr(docsrs, doc(cfg(feature = "socketcan-compat")))]
-impl Into<socketcan::CANFilter> for Filter {
-    fn into(self) -> socketcan::CANFilter {
-        socketcan::CANFilter::new(self.id.as_raw() | self.id.flags().bits(), self.mask.0).unwrap()
+/// Error returned when a range of identifiers cannot be expressed exactly by a single [`Filter`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RangeNotExpressible {
+    superset: Filter,
+}
+
+impl RangeNotExpressible {
+    /// Gets the filter that matches the smallest superset of the range that a single filter can
+    /// express.
+    pub const fn superset(&self) -> Filter {
+        self.superset
+    }
+}
+
+impl fmt::Display for RangeNotExpressible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "range cannot be expressed exactly by a single filter (closest: {} with mask {:#010X})",
+            self.superset.id, self.superset.mask.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for RangeNotExpressible {}
+
+/// Builder for incrementally constructing a [`Filter`].
+///
+/// While [`Filter`] provides helpers for common filters, composing them still requires reasoning
+/// about how the resulting masks interact.  [`FilterBuilder`] instead captures the high-level intent
+/// -- which identifiers to match, in which addressing mode, and which frame types -- and computes the
+/// resulting identifier and mask when the filter is built.
+///
+/// By default, a builder matches any identifier, in either addressing mode, for all frame types.
+///
+/// ## Usage
+///
+/// Matching the standard identifier 0x123, for data frames only:
+///
+/// ```
+/// # use can::identifier::{FilterBuilder, Id, StandardId};
+/// let id = Id::Standard(StandardId::new(0x123).unwrap());
+/// let filter = FilterBuilder::new()
+///     .id(id)
+///     .match_exact()
+///     .standard_only()
+///     .data_only()
+///     .build();
+///
+/// assert!(filter.matches(id));
+/// ```
+#[derive(Debug)]
+pub struct FilterBuilder {
+    id: Id,
+    matching: Matching,
+    addressing: Addressing,
+    data_only: bool,
+}
+
+#[derive(Debug)]
+enum Matching {
+    Any,
+    Exact,
+    Range(Id, Id),
+}
+
+#[derive(Debug)]
+enum Addressing {
+    Any,
+    Standard,
+    Extended,
+}
+
+impl FilterBuilder {
+    /// Creates a [`FilterBuilder`] that matches any identifier.
+    pub const fn new() -> Self {
+        Self {
+            id: Id::Standard(StandardId::ZERO),
+            matching: Matching::Any,
+            addressing: Addressing::Any,
+            data_only: false,
+        }
+    }
+
+    /// Sets the identifier to match.
+    ///
+    /// The identifier is only used when matching exactly, via [`match_exact`][Self::match_exact].
+    pub const fn id(self, id: Id) -> Self {
+        Self { id, ..self }
+    }
+
+    /// Matches only the configured identifier.
+    ///
+    /// This has the same semantics as [`Filter::from_identity`], such that the identifier is only
+    /// matched in its specific addressing mode unless overridden by
+    /// [`standard_only`][Self::standard_only] or [`extended_only`][Self::extended_only].
+    pub const fn match_exact(self) -> Self {
+        Self {
+            matching: Matching::Exact,
+            ..self
+        }
+    }
+
+    /// Matches any identifier between `start` and `end`, inclusive.
+    ///
+    /// This has the same semantics as [`Filter::range`].
+    pub const fn match_range(self, start: Id, end: Id) -> Self {
+        Self {
+            matching: Matching::Range(start, end),
+            ..self
+        }
+    }
+
+    /// Matches only frames using standard addressing.
+    pub const fn standard_only(self) -> Self {
+        Self {
+            addressing: Addressing::Standard,
+            ..self
+        }
+    }
+
+    /// Matches only frames using extended addressing.
+    ///
+    /// If the identifier being matched is a standard identifier, it is matched as an extended
+    /// identifier with the same value.
+    pub const fn extended_only(self) -> Self {
+        Self {
+            addressing: Addressing::Extended,
+            ..self
+        }
+    }
+
+    /// Matches only data frames.
+    pub const fn data_only(self) -> Self {
+        Self {
+            data_only: true,
+            ..self
+        }
+    }
+
+    /// Builds the [`Filter`].
+    pub const fn build(self) -> Filter {
+        let filter = match self.matching {
+            Matching::Any => Filter::any(),
+            Matching::Exact => Filter::from_identity(self.id),
+            Matching::Range(start, end) => Filter::range(start, end),
+        };
+
+        let filter = match self.addressing {
+            Addressing::Any => filter,
+            Addressing::Standard => filter.disallow_extended_frames(),
+            Addressing::Extended => {
+                let id = match filter.id {
+                    Id::Standard(sid) => Id::Extended(sid.as_extended_id()),
+                    Id::Extended(eid) => Id::Extended(eid),
+                };
+
+                Filter::new(id, Mask(filter.mask.0 | IdentifierFlags::EXTENDED.bits()))
+            }
+        };
+
+        if self.data_only {
+            filter.disallow_rtr_frames().disallow_error_frames()
+        } else {
+            filter
+        }
+    }
+}
+
+impl Default for FilterBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::identifier::{id::tests::arb_id, StandardId};
+    use std::{cmp::Ordering, collections::HashSet, string::ToString};
+
+    use crate::{
+        constants::IdentifierFlags,
+        identifier::{
+            id::tests::{arb_extendedid, arb_id, arb_standardid},
+            ExtendedId, Id, StandardId,
+        },
+    };
+
+    use super::{Filter, FilterBuilder, Mask, ParseMaskError};
+
+    use proptest::{
+        arbitrary::any as arb_any, collection::vec as arb_vec, proptest, strategy::Strategy,
+    };
+
+    const REFERENCE_FLAGS: [IdentifierFlags; 3] = [
+        IdentifierFlags::EXTENDED,
+        IdentifierFlags::REMOTE,
+        IdentifierFlags::ERROR,
+    ];
+
+    /// Reference model of a flags-only filter.
+    ///
+    /// Each flag is either ignored (`None`), or required to be set or unset (`Some`), in the same
+    /// order as `REFERENCE_FLAGS`.
+    #[derive(Clone, Copy, Debug)]
+    struct ReferenceFilter([Option<bool>; 3]);
 
-    use super::Filter;
+    impl ReferenceFilter {
+        fn matches(&self, id: Id) -> bool {
+            REFERENCE_FLAGS
+                .iter()
+                .zip(self.0.iter())
+                .all(|(flag, required)| match required {
+                    None => true,
+                    Some(set) => id.flags().contains(*flag) == *set,
+                })
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Toggle {
+        Allow(usize),
+        Disallow(usize),
+    }
 
-    use proptest::{collection::vec as arb_vec, proptest};
+    impl Toggle {
+        fn apply(self, filter: Filter, reference: &mut ReferenceFilter) -> Filter {
+            match self {
+                Toggle::Allow(i) => {
+                    reference.0[i] = None;
+                    match i {
+                        0 => filter.allow_extended_frames(),
+                        1 => filter.allow_rtr_frames(),
+                        _ => filter.allow_error_frames(),
+                    }
+                }
+                Toggle::Disallow(i) => {
+                    reference.0[i] = Some(false);
+                    match i {
+                        0 => filter.disallow_extended_frames(),
+                        1 => filter.disallow_rtr_frames(),
+                        _ => filter.disallow_error_frames(),
+                    }
+                }
+            }
+        }
+    }
+
+    fn base_filter(i: usize) -> (Filter, ReferenceFilter) {
+        match i {
+            0 => (Filter::any(), ReferenceFilter([None, None, None])),
+            1 => (
+                Filter::data_frames_only(),
+                ReferenceFilter([None, Some(false), Some(false)]),
+            ),
+            _ => (
+                Filter::error_frames_only(),
+                ReferenceFilter([None, None, Some(true)]),
+            ),
+        }
+    }
+
+    fn arb_toggle() -> impl Strategy<Value = Toggle> {
+        (arb_any::<bool>(), 0usize..3).prop_map(|(allow, i)| {
+            if allow {
+                Toggle::Allow(i)
+            } else {
+                Toggle::Disallow(i)
+            }
+        })
+    }
 
     proptest! {
+        #[test]
+        fn identity_respects_addressing_mode(
+            sid in arb_standardid(),
+            eids in arb_vec(arb_extendedid(), 100..1000),
+        ) {
+            let filter = Filter::from_identity(Id::Standard(sid));
+            assert!(filter.matches(Id::Standard(sid)));
+
+            // Extended identifiers that share the same lower bits as the standard identifier.
+            let aliased = ExtendedId::with_flags(u32::from(sid.as_raw()), sid.flags()).unwrap();
+            assert!(!filter.matches(Id::Extended(aliased)));
+
+            for eid in eids {
+                assert!(!filter.matches(Id::Extended(eid)));
+                assert!(!Filter::from_identity(Id::Extended(eid)).matches(Id::Standard(sid)));
+            }
+        }
+
         #[test]
         fn none(ids in arb_vec(arb_id(), 100..1000)) {
             let filter = Filter::none();
@@ -290,6 +994,152 @@ pub(crate) mod tests {
                 assert!(filter.matches(id));
             }
         }
+
+        #[test]
+        fn builder_matches_equivalents(ids in arb_vec(arb_id(), 100..1000)) {
+            let sid = StandardId::new(0x123).unwrap();
+            let start = Id::Standard(StandardId::new(0x7E0).unwrap());
+            let end = Id::Standard(StandardId::new(0x7EF).unwrap());
+
+            let pairs = [
+                (FilterBuilder::new().build(), Filter::any()),
+                (FilterBuilder::new().data_only().build(), Filter::data_frames_only()),
+                (
+                    FilterBuilder::new().standard_only().build(),
+                    Filter::any().disallow_extended_frames(),
+                ),
+                (
+                    FilterBuilder::new().extended_only().build(),
+                    Filter::new(
+                        Id::Extended(ExtendedId::ZERO),
+                        Mask::new(IdentifierFlags::EXTENDED.bits()),
+                    ),
+                ),
+                (
+                    FilterBuilder::new().id(sid.into()).match_exact().build(),
+                    Filter::from_identity(sid.into()),
+                ),
+                (
+                    FilterBuilder::new()
+                        .id(sid.into())
+                        .match_exact()
+                        .standard_only()
+                        .data_only()
+                        .build(),
+                    Filter::from_identity(sid.into())
+                        .disallow_extended_frames()
+                        .disallow_rtr_frames()
+                        .disallow_error_frames(),
+                ),
+                (
+                    FilterBuilder::new().match_range(start, end).build(),
+                    Filter::range(start, end),
+                ),
+            ];
+
+            for (built, expected) in &pairs {
+                assert_eq!(expected, built);
+            }
+
+            for id in ids {
+                for (built, expected) in &pairs {
+                    assert_eq!(expected.matches(id), built.matches(id), "id: {}", id);
+                }
+            }
+        }
+
+        #[test]
+        fn flags_match_reference(
+            base in 0usize..3,
+            toggles in arb_vec(arb_toggle(), 0..8),
+            ids in arb_vec(arb_id(), 100..1000),
+        ) {
+            let (base, mut reference) = base_filter(base);
+            let filter = toggles
+                .into_iter()
+                .fold(base, |filter, toggle| toggle.apply(filter, &mut reference));
+
+            for id in ids {
+                assert_eq!(reference.matches(id), filter.matches(id), "id: {}", id);
+            }
+        }
+    }
+
+    #[test]
+    fn data_frames_only_rejects_remote_and_error_frames() {
+        let filter = Filter::data_frames_only();
+        let id = StandardId::new(0x123).unwrap();
+
+        assert!(filter.matches(id.into()));
+        assert!(filter.matches(id.as_extended_id().into()));
+        assert!(!filter.matches(id.set_flags(IdentifierFlags::REMOTE).into()));
+        assert!(!filter.matches(id.set_flags(IdentifierFlags::ERROR).into()));
+    }
+
+    #[test]
+    fn dedup() {
+        let filters = [
+            Filter::any(),
+            Filter::data_frames_only(),
+            Filter::any(),
+            Filter::error_frames_only(),
+            Filter::data_frames_only(),
+        ];
+
+        let unique = filters.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(3, unique.len());
+    }
+
+    #[test]
+    fn j1939_pgn() {
+        // PGN 65265 (0xFEF1), cruise control/vehicle speed, is a PDU2 PGN.
+        let filter = Filter::j1939_pgn(0xFEF1);
+
+        assert!(filter.matches(ExtendedId::new(0x18FEF100).unwrap().into()));
+        assert!(filter.matches(ExtendedId::new(0x18FEF117).unwrap().into()));
+        assert!(filter.matches(ExtendedId::new(0x0CFEF117).unwrap().into()));
+        assert!(!filter.matches(ExtendedId::new(0x18FEF200).unwrap().into()));
+        assert!(!filter.matches(StandardId::new(0x100).unwrap().into()));
+
+        // PGN 59904 (0xEA00), request, is a PDU1 PGN, so the destination address is ignored.
+        let filter = Filter::j1939_pgn(0xEA00);
+
+        assert!(filter.matches(ExtendedId::new(0x18EA00F9).unwrap().into()));
+        assert!(filter.matches(ExtendedId::new(0x18EAFF17).unwrap().into()));
+        assert!(!filter.matches(ExtendedId::new(0x18EB00F9).unwrap().into()));
+    }
+
+    #[test]
+    fn builder_exact_match() {
+        let sid = StandardId::new(0x123).unwrap();
+        let filter = FilterBuilder::new()
+            .id(sid.into())
+            .match_exact()
+            .data_only()
+            .build();
+
+        assert!(filter.matches(sid.into()));
+        assert!(!filter.matches(StandardId::new(0x124).unwrap().into()));
+        assert!(!filter.matches(sid.as_extended_id().into()));
+        assert!(!filter.matches(sid.set_flags(IdentifierFlags::REMOTE).into()));
+
+        let filter = FilterBuilder::new()
+            .id(sid.into())
+            .match_exact()
+            .extended_only()
+            .build();
+
+        assert!(!filter.matches(sid.into()));
+        assert!(filter.matches(sid.as_extended_id().into()));
+    }
+
+    #[test]
+    fn disallow_extended_frames() {
+        let filter = Filter::data_frames_only().disallow_extended_frames();
+        let id = StandardId::new(0x123).unwrap();
+
+        assert!(filter.matches(id.into()));
+        assert!(!filter.matches(id.as_extended_id().into()));
     }
 
     #[test]
@@ -348,4 +1198,348 @@ pub(crate) mod tests {
             assert!(filter.matches(id.into()));
         }
     }
+
+    #[test]
+    fn mask_covering() {
+        assert_eq!(Mask::ALL, Mask::covering(0));
+        assert_eq!(Mask::new(0xFFFFFFF0), Mask::covering(0xF));
+        assert_eq!(Mask::new(0xFFFFFFF0), Mask::covering(0xA));
+        assert_eq!(Mask::new(0xFFFFFF00), Mask::covering(0x80));
+        assert_eq!(Mask::NONE, Mask::covering(0xFFFFFFFF));
+
+        let sid = |raw| Id::Standard(StandardId::new(raw).unwrap());
+
+        // A power-of-two span from an aligned base matches exactly.
+        let filter = Filter::new(sid(0x7E0), Mask::covering(0xF));
+        assert!(!filter.matches(sid(0x7DF)));
+        for raw in 0x7E0..=0x7EF {
+            assert!(filter.matches(sid(raw)));
+        }
+        assert!(!filter.matches(sid(0x7F0)));
+
+        // Any other span over-matches, up to the end of the aligned power-of-two block.
+        let filter = Filter::new(sid(0x7E0), Mask::covering(0xA));
+        for raw in 0x7E0..=0x7EA {
+            assert!(filter.matches(sid(raw)));
+        }
+        for raw in 0x7EB..=0x7EF {
+            assert!(filter.matches(sid(raw)));
+        }
+        assert!(!filter.matches(sid(0x7F0)));
+
+        // An unaligned base matches the aligned block containing it, not the span after it.
+        let filter = Filter::new(sid(0x7E7), Mask::covering(1));
+        assert!(filter.matches(sid(0x7E6)));
+        assert!(filter.matches(sid(0x7E7)));
+        assert!(!filter.matches(sid(0x7E8)));
+    }
+
+    #[test]
+    fn range_over_matches() {
+        let sid = |raw| Id::Standard(StandardId::new(raw).unwrap());
+
+        // Every identifier in the range is matched, along with the rest of the aligned block.
+        let filter = Filter::range(sid(0x7E0), sid(0x7EA));
+        for raw in 0x7E0..=0x7EF {
+            assert!(filter.matches(sid(raw)));
+        }
+        assert!(!filter.matches(sid(0x7DF)));
+        assert!(!filter.matches(sid(0x7F0)));
+
+        // Unaligned ranges that cross a power-of-two boundary widen to the enclosing block.
+        let filter = Filter::range(sid(0x7E7), sid(0x7E8));
+        for raw in 0x7E0..=0x7EF {
+            assert!(filter.matches(sid(raw)));
+        }
+    }
+
+    #[test]
+    fn try_range() {
+        let sid = |raw| Id::Standard(StandardId::new(raw).unwrap());
+
+        let filter = Filter::try_range(sid(0x7E0), sid(0x7EF)).unwrap();
+        assert_eq!(Filter::range(sid(0x7E0), sid(0x7EF)), filter);
+        assert!(Filter::try_range(sid(0x7E8), sid(0x7EF)).is_ok());
+        assert!(Filter::try_range(sid(0x7E8), sid(0x7E8)).is_ok());
+
+        let err = Filter::try_range(sid(0x7E0), sid(0x7EA)).unwrap_err();
+        assert_eq!(Filter::range(sid(0x7E0), sid(0x7EA)), err.superset());
+        assert!(err.superset().matches(sid(0x7EF)));
+
+        // Right size, but not aligned.
+        assert!(Filter::try_range(sid(0x7E4), sid(0x7EB)).is_err());
+    }
+
+    #[test]
+    fn specificity() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let identity = Filter::from_identity(id);
+
+        assert_eq!(32, identity.specificity());
+        assert_eq!(0, Filter::any().specificity());
+        assert_eq!(2, Filter::data_frames_only().specificity());
+        assert_eq!(31, identity.allow_rtr_frames().specificity());
+
+        assert_eq!(
+            Ordering::Greater,
+            Filter::by_specificity(&identity, &Filter::any())
+        );
+        assert_eq!(
+            Ordering::Less,
+            Filter::by_specificity(&Filter::any(), &identity)
+        );
+        assert_eq!(
+            Ordering::Equal,
+            Filter::by_specificity(&identity, &Filter::none())
+        );
+    }
+
+    #[test]
+    fn is_empty() {
+        let id = Id::Standard(StandardId::new(0x7E8).unwrap());
+
+        assert!(Filter::none().is_empty());
+        assert!(!Filter::any().is_empty());
+        assert!(!Filter::from_identity(id).is_empty());
+        assert!(!Filter::error_frames_only().is_empty());
+
+        // Requiring both the remote and error flags can never match.
+        let remote_error = id.set_flags(IdentifierFlags::REMOTE | IdentifierFlags::ERROR);
+        assert!(Filter::from_identity(remote_error).is_empty());
+        assert!(!Filter::from_identity(remote_error)
+            .allow_rtr_frames()
+            .is_empty());
+        assert!(!Filter::from_identity(remote_error)
+            .allow_error_frames()
+            .is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn is_empty_never_matches(
+            filter_id in arb_id(),
+            mask in arb_any::<u32>(),
+            id in arb_id().prop_filter("frames are never both remote and error frames", |id| {
+                !id.flags().contains(IdentifierFlags::REMOTE | IdentifierFlags::ERROR)
+            }),
+        ) {
+            let filter = Filter::new(filter_id, Mask::new(mask));
+            if filter.is_empty() {
+                assert!(!filter.matches(id));
+            }
+        }
+    }
+
+    #[test]
+    fn match_count() {
+        let sid = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let eid = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+
+        assert_eq!(1, Filter::from_identity(sid).match_count());
+        assert_eq!(1, Filter::from_identity(eid).match_count());
+        assert_eq!(
+            2,
+            Filter::new(sid, Mask::ALL - Mask::new(0x1)).match_count()
+        );
+        assert_eq!(
+            8,
+            Filter::range(sid, Id::Standard(StandardId::new(0x7EF).unwrap())).match_count()
+        );
+        assert_eq!(0, Filter::none().match_count());
+
+        // Ignoring the addressing mode matches both a standard and an extended identifier.
+        let any_mode = Filter::from_identity(sid).allow_extended_frames();
+        assert_eq!(2, any_mode.match_count());
+
+        // Ignoring frame types doesn't change the number of identifiers.
+        assert_eq!(
+            1,
+            Filter::from_identity(sid).allow_rtr_frames().match_count()
+        );
+
+        assert_eq!(2048 + (1 << 29), Filter::any().match_count());
+        assert_eq!(2048 + (1 << 29), Filter::data_frames_only().match_count());
+    }
+
+    proptest! {
+        #[test]
+        fn match_count_standard(filter_id in arb_id(), mask in arb_any::<u32>()) {
+            // Only check filters that don't allow extended identifiers, so that the identifiers
+            // can be enumerated.
+            let filter = Filter::new(filter_id, Mask::new(mask)).disallow_extended_frames();
+            let expected = (0..=0x7FF)
+                .filter(|raw| {
+                    let id = Id::Standard(StandardId::new(*raw).unwrap()).set_flags(filter_id.flags());
+                    filter.matches(id)
+                })
+                .count() as u64;
+
+            let expected = if filter.is_empty() { 0 } else { expected };
+            assert_eq!(expected, filter.match_count());
+        }
+    }
+
+    #[test]
+    fn subsumes() {
+        let sid = Id::Standard(StandardId::new(0x7E8).unwrap());
+        let identity = Filter::from_identity(sid);
+        let range = Filter::range(sid, Id::Standard(StandardId::new(0x7EF).unwrap()));
+
+        assert!(Filter::any().subsumes(&identity));
+        assert!(range.subsumes(&identity));
+        assert!(identity.subsumes(&identity));
+        assert!(!identity.subsumes(&range));
+        assert!(!identity.subsumes(&Filter::any()));
+        assert!(identity.allow_rtr_frames().subsumes(&identity));
+        assert!(!identity.subsumes(&identity.allow_rtr_frames()));
+
+        // A filter that never matches is subsumed by every filter.
+        assert!(identity.subsumes(&Filter::none()));
+        assert!(!Filter::none().subsumes(&identity));
+    }
+
+    proptest! {
+        #[test]
+        fn subsumes_matches(
+            a_id in arb_id(),
+            a_mask in arb_any::<u32>(),
+            b_id in arb_id(),
+            b_mask in arb_any::<u32>(),
+            id in arb_id(),
+        ) {
+            // Use masks that share most bits so that subsumption is more likely.
+            let a = Filter::new(a_id, Mask::new(a_mask));
+            let b = Filter::new(a_id.set_flags(b_id.flags()), Mask::new(a_mask | b_mask));
+            if a.subsumes(&b) && b.matches(id) {
+                assert!(a.matches(id));
+            }
+        }
+    }
+
+    #[test]
+    fn from_raw() {
+        let filter = Filter::from_raw(0x7E8, 0xFFFFFFF8);
+        assert_eq!(Id::Standard(StandardId::new(0x7E8).unwrap()), filter.id());
+        assert_eq!(Mask::new(0xFFFFFFF8), filter.mask());
+        assert_eq!((0x7E8, 0xFFFFFFF8), filter.to_raw());
+
+        let filter = Filter::from_raw(0x98DAF110, 0x9FFFFFFF);
+        assert_eq!(
+            Id::Extended(ExtendedId::new(0x18DAF110).unwrap()),
+            filter.id()
+        );
+        assert_eq!((0x98DAF110, 0x9FFFFFFF), filter.to_raw());
+
+        let filter = Filter::from_raw(0x2000_0000, 0x2000_0000);
+        assert_eq!(Filter::error_frames_only(), filter);
+    }
+
+    proptest! {
+        #[test]
+        fn from_raw_round_trip(filter_id in arb_id(), mask in arb_any::<u32>()) {
+            let filter = Filter::new(filter_id, Mask::new(mask));
+            let (raw_id, raw_mask) = filter.to_raw();
+            assert_eq!(filter, Filter::from_raw(raw_id, raw_mask));
+
+            #[cfg(feature = "socketcan-compat")]
+            {
+                let expected = socketcan::CanFilter::new(raw_id, raw_mask);
+                let actual = socketcan::CanFilter::from(Filter::from_raw(raw_id, raw_mask));
+                assert_eq!(expected, actual);
+            }
+        }
+    }
+
+    #[test]
+    fn addressing_identity() {
+        const EXTENDED: Filter = Filter::extended_identity(crate::ext_id!(0x123));
+        let sid = StandardId::new(0x123).unwrap();
+        let eid = ExtendedId::new(0x123).unwrap();
+
+        assert!(EXTENDED.matches(Id::Extended(eid)));
+        assert!(!EXTENDED.matches(Id::Standard(sid)));
+        assert_eq!(Filter::from_identity(Id::Extended(eid)), EXTENDED);
+
+        let standard = Filter::standard_identity(sid);
+        assert!(standard.matches(Id::Standard(sid)));
+        assert!(!standard.matches(Id::Extended(eid)));
+        assert_eq!(Filter::from_identity(Id::Standard(sid)), standard);
+    }
+
+    proptest! {
+        #[test]
+        fn mask_from_u32(raw in arb_any::<u32>()) {
+            let mask = Mask::from(raw);
+            assert_eq!(Mask::new(raw), mask);
+            assert_eq!(raw, u32::from(mask));
+            assert_eq!(mask.as_raw(), mask.into());
+        }
+
+        #[test]
+        fn mask_assign_ops(a in arb_any::<u32>(), b in arb_any::<u32>()) {
+            let (a, b) = (Mask::new(a), Mask::new(b));
+
+            let mut mask = a;
+            mask &= b;
+            assert_eq!(a & b, mask);
+
+            let mut mask = a;
+            mask |= b;
+            assert_eq!(a | b, mask);
+
+            let mut mask = a;
+            mask ^= b;
+            assert_eq!(a ^ b, mask);
+
+            let mut mask = a;
+            mask += b;
+            assert_eq!(a + b, mask);
+
+            let mut mask = a;
+            mask -= b;
+            assert_eq!(a - b, mask);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_precomputed(filter_id in arb_id(), mask in arb_any::<u32>(), id in arb_id()) {
+            let filter = Filter::new(filter_id, Mask::new(mask));
+            let expected = id.to_raw_with_flags() & mask == filter_id.to_raw_with_flags() & mask;
+            assert!(filter.matches(id) == expected);
+        }
+    }
+
+    #[test]
+    fn mask_from_str() {
+        assert_eq!(Ok(Mask::new(0xFFFFFFF8)), "0xFFFFFFF8".parse());
+        assert_eq!(Ok(Mask::new(0xFFFFFFF8)), "0Xfffffff8".parse());
+        assert_eq!(Ok(Mask::new(0x7FF)), "7ff".parse());
+        assert_eq!(Ok(Mask::NONE), "0".parse());
+        assert_eq!("0x000007FF", Mask::new(0x7FF).to_string());
+
+        assert_eq!(Err(ParseMaskError::Empty), "".parse::<Mask>());
+        assert_eq!(Err(ParseMaskError::Empty), "0x".parse::<Mask>());
+        assert_eq!(Err(ParseMaskError::InvalidDigit), "0xFG".parse::<Mask>());
+        assert_eq!(Err(ParseMaskError::InvalidDigit), "+7FF".parse::<Mask>());
+        assert_eq!(Err(ParseMaskError::InvalidDigit), " 7FF".parse::<Mask>());
+        assert_eq!(Err(ParseMaskError::TooLong), "0x1FFFFFFFF".parse::<Mask>());
+    }
+
+    proptest! {
+        #[test]
+        fn mask_display_round_trip(raw in arb_any::<u32>()) {
+            let mask = Mask::new(raw);
+            assert!(mask.to_string().parse::<Mask>() == Ok(mask));
+        }
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<Mask>();
+        assert_format::<Filter>();
+    }
 }
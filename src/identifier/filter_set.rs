@@ -0,0 +1,504 @@
+use alloc::vec::Vec;
+use core::{iter::FromIterator, slice};
+
+use crate::constants::{EFF_FLAG, EFF_MASK, SFF_MASK};
+
+use super::{ExtendedId, Filter, Id, Mask, StandardId};
+
+// Number of filters compared at a time when matching an identifier.
+const MATCH_CHUNK_LEN: usize = 8;
+
+/// A set of filters.
+///
+/// An identifier is matched by the set if it is matched by any of the filters in the set.  This
+/// allows matching identifiers that cannot be matched by a single [`Filter`], such as arbitrary
+/// ranges of identifiers.
+///
+/// Alongside the filters themselves, the set keeps the masked identifier and mask of each filter in
+/// two parallel arrays of raw values, such that matching an identifier only walks two contiguous
+/// slices of integers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FilterSet {
+    filters: Vec<Filter>,
+    masked_ids: Vec<u32>,
+    masks: Vec<u32>,
+}
+
+impl FilterSet {
+    /// Creates an empty [`FilterSet`].
+    ///
+    /// An empty set matches no identifiers.
+    pub const fn new() -> Self {
+        Self {
+            filters: Vec::new(),
+            masked_ids: Vec::new(),
+            masks: Vec::new(),
+        }
+    }
+
+    /// Adds a filter to the set.
+    pub fn push(&mut self, filter: Filter) {
+        let (id, mask) = filter.to_raw();
+        self.filters.push(filter);
+        self.masked_ids.push(id & mask);
+        self.masks.push(mask);
+    }
+
+    /// Gets the number of filters in the set.
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Whether or not the set has no filters.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Gets an iterator over the filters in the set.
+    pub fn iter(&self) -> FilterSetIter<'_> {
+        FilterSetIter {
+            inner: self.filters.iter(),
+        }
+    }
+
+    /// Checks if the given identifier is matched by any filter in the set.
+    pub fn matches(&self, id: Id) -> bool {
+        let raw = id.to_raw_with_flags();
+
+        // Filters are compared in fixed-size chunks without branching, which lets the comparisons
+        // within a chunk be vectorized, while still stopping at the first chunk with a match.
+        self.masks
+            .chunks(MATCH_CHUNK_LEN)
+            .zip(self.masked_ids.chunks(MATCH_CHUNK_LEN))
+            .any(|(masks, masked_ids)| {
+                masks
+                    .iter()
+                    .zip(masked_ids)
+                    .fold(false, |matched, (mask, masked_id)| {
+                        matched | (raw & mask == *masked_id)
+                    })
+            })
+    }
+
+    /// Gets the indices of every filter in the set that matches the given identifier.
+    ///
+    /// Indices are yielded in ascending order, and correspond to the order of the filters as
+    /// yielded by [`iter`][Self::iter].
+    pub fn matches_all_indices(&self, id: Id) -> impl Iterator<Item = usize> + '_ {
+        let raw = id.to_raw_with_flags();
+        self.masks
+            .iter()
+            .zip(&self.masked_ids)
+            .enumerate()
+            .filter(move |(_, (mask, masked_id))| raw & *mask == **masked_id)
+            .map(|(i, _)| i)
+    }
+
+    /// Creates a new [`FilterSet`] with the filters of both this set and `other`.
+    ///
+    /// The resulting set matches every identifier matched by either set.
+    pub fn union(&self, other: &FilterSet) -> FilterSet {
+        let mut union = self.clone();
+        union.extend(other);
+        union
+    }
+
+    /// Retains only the filters for which the given predicate returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Filter) -> bool,
+    {
+        self.filters.retain(|filter| f(filter));
+        self.rebuild_layout();
+    }
+
+    /// Removes any filters that are subsumed by another filter in the set.
+    ///
+    /// A filter is removed when another filter in the set matches every identifier that it matches,
+    /// as determined by [`Filter::subsumes`], and so the set of identifiers matched by the set does
+    /// not change.  When multiple filters are equivalent, the first one is kept.  The order of the
+    /// remaining filters is preserved.
+    ///
+    /// This is useful for keeping filter tables small before handing them to the kernel or a
+    /// controller, which may only support a limited number of filters.
+    pub fn minimize(&mut self) {
+        let filters = &self.filters;
+        let redundant = filters
+            .iter()
+            .enumerate()
+            .map(|(i, filter)| {
+                filters.iter().enumerate().any(|(j, other)| {
+                    j != i && other.subsumes(filter) && (j < i || !filter.subsumes(other))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut redundant = redundant.into_iter();
+        self.filters
+            .retain(|_| !redundant.next().expect("one entry per filter"));
+        self.rebuild_layout();
+    }
+
+    // Rebuilds the raw identifier and mask arrays from the filters, after filters are removed.
+    fn rebuild_layout(&mut self) {
+        let filters = core::mem::take(&mut self.filters);
+        self.masked_ids.clear();
+        self.masks.clear();
+        self.extend(filters);
+    }
+}
+
+impl FromIterator<Filter> for FilterSet {
+    fn from_iter<I: IntoIterator<Item = Filter>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Filter> for FilterSet {
+    fn extend<I: IntoIterator<Item = Filter>>(&mut self, iter: I) {
+        for filter in iter {
+            self.push(filter);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a FilterSet {
+    type Item = Filter;
+    type IntoIter = FilterSetIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the filters in a [`FilterSet`].
+#[derive(Clone, Debug)]
+pub struct FilterSetIter<'a> {
+    inner: slice::Iter<'a, Filter>,
+}
+
+impl Iterator for FilterSetIter<'_> {
+    type Item = Filter;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for FilterSetIter<'_> {}
+
+impl Filter {
+    /// Creates a [`FilterSet`] that will match exactly the identifiers between `start` and `end`,
+    /// inclusive.
+    ///
+    /// The range is split into the fewest aligned power-of-two blocks that cover it, each of which
+    /// is expressed exactly by a single filter.  For example, a range of 0x7E0 to 0x7EA is split
+    /// into 0x7E0 to 0x7E7, 0x7E8 to 0x7E9, and 0x7EA.  Ranges that can be expressed exactly by a
+    /// single filter, as with [`try_range`][Self::try_range], result in a set with one filter.
+    ///
+    /// The addressing mode and flags of the filters are taken from the lower of `start` and `end`.
+    pub fn range_exact(start: Id, end: Id) -> FilterSet {
+        let (base, low, high) = if start.as_raw() > end.as_raw() {
+            (end, end.as_raw(), start.as_raw())
+        } else {
+            (start, start.as_raw(), end.as_raw())
+        };
+
+        let mut filters = FilterSet::new();
+        let mut block_start = u64::from(low);
+        while block_start <= u64::from(high) {
+            // The largest block that is aligned to the start of the block, and does not go past the
+            // end of the range.
+            let mut block_len = if block_start == 0 {
+                1 << 32
+            } else {
+                1 << block_start.trailing_zeros()
+            };
+            while block_start + block_len - 1 > u64::from(high) {
+                block_len >>= 1;
+            }
+
+            let id = with_raw(base, block_start as u32);
+            filters.push(Filter::new(id, Mask::covering((block_len - 1) as u32)));
+            block_start += block_len;
+        }
+
+        filters
+    }
+
+    /// Creates a [`FilterSet`] that will match exactly the identifiers that this filter does not
+    /// match.
+    ///
+    /// Each bit covered by the mask yields at most one filter in the set: walking the mask from the
+    /// most significant bit down, each filter requires the bits above the current bit to match this
+    /// filter, and the current bit to differ from it.  As such, the set holds up to one filter for
+    /// each bit in the mask, and so negating a filter with a mask covering all 32 bits, such as
+    /// [`from_identity`][Self::from_identity], can result in a few dozen filters.  Filters that
+    /// could never match an identifier are left out: those that require bits beyond the lower 11
+    /// bits of a standard identifier, or that require both the remote and error flags.
+    ///
+    /// Negating a filter that matches every identifier, such as [`any`][Self::any], results in an
+    /// empty set.
+    pub fn negate(&self) -> FilterSet {
+        let extended_bits = EFF_MASK & !SFF_MASK;
+        let (filter_id, mask) = self.to_raw();
+
+        let mut filters = FilterSet::new();
+        let mut required = 0;
+        for bit in (0..u32::BITS).rev().map(|bit| 1 << bit) {
+            if mask & bit == 0 {
+                continue;
+            }
+
+            let raw = (filter_id ^ bit) & (required | bit);
+            required |= bit;
+
+            // Identifier bits above the lower 11 bits can only be set for extended identifiers, so
+            // if the extended flag is also required to be unset, nothing can match.
+            let needs_extended = raw & extended_bits != 0;
+            if needs_extended && required & EFF_FLAG != 0 && raw & EFF_FLAG == 0 {
+                continue;
+            }
+
+            let raw = if needs_extended { raw | EFF_FLAG } else { raw };
+            let filter = Filter::new(Id::from_raw_with_flags(raw), Mask::new(required));
+            if !filter.is_empty() {
+                filters.push(filter);
+            }
+        }
+
+        filters
+    }
+}
+
+// Creates an identifier with the same addressing mode and flags as `base`, but the given raw value.
+fn with_raw(base: Id, raw: u32) -> Id {
+    match base {
+        Id::Standard(sid) => {
+            Id::Standard(StandardId::new_masked(raw as u16).set_flags(sid.flags()))
+        }
+        Id::Extended(eid) => Id::Extended(ExtendedId::new_masked(raw).set_flags(eid.flags())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use proptest::{
+        arbitrary::any as arb_any, collection::vec as arb_vec, proptest, strategy::Strategy,
+    };
+
+    use crate::{
+        constants::IdentifierFlags,
+        identifier::{id::tests::arb_id, ExtendedId, Filter, Id, Mask, StandardId},
+    };
+
+    use super::FilterSet;
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    #[test]
+    fn matches() {
+        let empty = FilterSet::new();
+        assert!(empty.is_empty());
+        assert!(!empty.matches(sid(0x123)));
+
+        let set = [0x123, 0x456]
+            .into_iter()
+            .map(|raw| Filter::from_identity(sid(raw)))
+            .collect::<FilterSet>();
+        assert_eq!(2, set.len());
+        assert!(set.matches(sid(0x123)));
+        assert!(set.matches(sid(0x456)));
+        assert!(!set.matches(sid(0x124)));
+        assert_eq!(2, set.iter().count());
+    }
+
+    #[test]
+    fn range_exact() {
+        let set = Filter::range_exact(sid(0x7E0), sid(0x7EA));
+        assert_eq!(3, set.len());
+        for raw in 0..=0x7FF {
+            assert_eq!(
+                (0x7E0..=0x7EA).contains(&raw),
+                set.matches(sid(raw)),
+                "mismatch for {:#X}",
+                raw
+            );
+        }
+
+        let set = Filter::range_exact(sid(0x7EF), sid(0x7E0));
+        assert_eq!(1, set.len());
+
+        let set = Filter::range_exact(sid(0x001), sid(0x7FE));
+        for raw in 0..=0x7FF {
+            assert_eq!((0x001..=0x7FE).contains(&raw), set.matches(sid(raw)));
+        }
+
+        let set = Filter::range_exact(sid(0), sid(0x7FF));
+        assert_eq!(1, set.len());
+        assert!(set.matches(sid(0)) && set.matches(sid(0x7FF)));
+    }
+
+    #[test]
+    fn collection() {
+        let mut set = FilterSet::new();
+        set.extend([Filter::from_identity(sid(0x123))]);
+
+        let other = [0x456, 0x789]
+            .into_iter()
+            .map(|raw| Filter::from_identity(sid(raw)))
+            .collect::<FilterSet>();
+        let union = set.union(&other);
+        assert_eq!(3, union.len());
+        for raw in [0x123, 0x456, 0x789] {
+            assert!(union.matches(sid(raw)));
+        }
+
+        set.extend(&other);
+        assert_eq!(union, set);
+
+        let mut count = 0;
+        for filter in &set {
+            assert!(filter.matches(filter.id()));
+            count += 1;
+        }
+        assert_eq!(3, count);
+
+        set.retain(|filter| filter.id() != sid(0x456));
+        assert_eq!(2, set.len());
+        assert!(!set.matches(sid(0x456)));
+    }
+
+    #[test]
+    fn minimize() {
+        let eid = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let mut set = [
+            Filter::from_identity(sid(0x7E9)),
+            Filter::range(sid(0x7E8), sid(0x7EF)),
+            Filter::from_identity(eid),
+            Filter::range(sid(0x7E8), sid(0x7EF)),
+            Filter::none(),
+            Filter::from_identity(sid(0x7E0)),
+        ]
+        .into_iter()
+        .collect::<FilterSet>();
+        let original = set.clone();
+
+        set.minimize();
+        let expected = [
+            Filter::range(sid(0x7E8), sid(0x7EF)),
+            Filter::from_identity(eid),
+            Filter::from_identity(sid(0x7E0)),
+        ]
+        .into_iter()
+        .collect::<FilterSet>();
+        assert_eq!(expected, set);
+
+        for raw in 0..=0x7FF {
+            assert_eq!(original.matches(sid(raw)), set.matches(sid(raw)));
+        }
+        assert!(set.matches(eid));
+    }
+
+    #[test]
+    fn negate() {
+        let id = sid(0x123);
+        let negated = Filter::from_identity(id).negate();
+        assert!(!negated.matches(id));
+        for raw in (0..=0x7FF).filter(|&raw| raw != 0x123) {
+            assert!(negated.matches(sid(raw)), "{:#X} not matched", raw);
+        }
+        assert!(negated.matches(Id::Extended(ExtendedId::new(0x123).unwrap())));
+        assert!(negated.matches(id.set_flags(IdentifierFlags::REMOTE)));
+        assert!(negated.matches(id.set_flags(IdentifierFlags::ERROR)));
+
+        // Only the addressing mode, the remote and error flags, and the lower 11 bits can differ.
+        assert_eq!(14, negated.len());
+
+        assert!(Filter::any().negate().is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn negate_is_complement(
+            filter_id in arb_id(),
+            mask in arb_any::<u32>(),
+            id in arb_id().prop_filter("frames are never both remote and error frames", |id| {
+                !id.flags().contains(IdentifierFlags::REMOTE | IdentifierFlags::ERROR)
+            }),
+        ) {
+            let filter = Filter::new(filter_id, Mask::new(mask));
+            assert!(filter.matches(id) != filter.negate().matches(id));
+        }
+    }
+
+    #[test]
+    fn matches_all_indices() {
+        let set = [
+            Filter::range(sid(0x7E0), sid(0x7EF)),
+            Filter::from_identity(sid(0x123)),
+            Filter::from_identity(sid(0x7E8)),
+            Filter::any(),
+        ]
+        .into_iter()
+        .collect::<FilterSet>();
+
+        assert_eq!(
+            vec![0, 2, 3],
+            set.matches_all_indices(sid(0x7E8)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![3],
+            set.matches_all_indices(sid(0x456)).collect::<Vec<_>>()
+        );
+
+        let mut set = set;
+        set.retain(|filter| *filter != Filter::any());
+        assert_eq!(0, set.matches_all_indices(sid(0x456)).count());
+        assert!(!set.matches(sid(0x456)));
+        assert_eq!(
+            vec![1],
+            set.matches_all_indices(sid(0x123)).collect::<Vec<_>>()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_naive(
+            filters in arb_vec((arb_id(), arb_any::<u32>()), 16..64),
+            ids in arb_vec(arb_id(), 100),
+        ) {
+            let filters = filters
+                .into_iter()
+                .map(|(id, mask)| Filter::new(id, Mask::new(mask)))
+                .collect::<Vec<_>>();
+            let mut set = filters.iter().copied().collect::<FilterSet>();
+
+            for id in &ids {
+                let expected = filters
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, filter)| filter.matches(*id))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                assert!(set.matches(*id) != expected.is_empty());
+                assert!(set.matches_all_indices(*id).collect::<Vec<_>>() == expected);
+            }
+
+            set.minimize();
+            for id in ids {
+                assert!(set.matches(id) == filters.iter().any(|filter| filter.matches(id)));
+            }
+        }
+    }
+}
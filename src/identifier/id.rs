@@ -1,6 +1,9 @@
-use std::{cmp, fmt};
+use core::{cmp, fmt, ops::RangeInclusive};
 
-use crate::constants::IdentifierFlags;
+use crate::constants::{IdentifierFlags, EFF_MASK, SFF_MASK};
+
+// Number of bits in an extended identifier.
+const EXTENDED_ID_BITS: u32 = 29;
 
 /// Standard (11-bit) CAN identifier.
 ///
@@ -28,6 +31,19 @@ impl StandardId {
         flags: IdentifierFlags::empty(),
     };
 
+    /// Minimum value for a standard identifier.
+    ///
+    /// This is an alias of [`ZERO`][Self::ZERO], for code that expects integer-like types to
+    /// define a `MIN`.  As with `ZERO`, this is the highest priority standard identifier.
+    pub const MIN: Self = Self::ZERO;
+
+    /// Range of all valid standard identifiers, from [`MIN`][Self::MIN] to [`MAX`][Self::MAX],
+    /// inclusive.
+    ///
+    /// Identifiers are ordered by their flags after their raw value, so an identifier at the upper
+    /// bound with the remote or error flags set falls outside of this range.
+    pub const RANGE: RangeInclusive<Self> = RangeInclusive::new(Self::MIN, Self::MAX);
+
     /// Creates a `StandardId`.
     ///
     /// Returns `None` if `identifier` is greater than [`MAX`][Self::MAX].
@@ -43,6 +59,18 @@ impl StandardId {
         }
     }
 
+    /// Creates a `StandardId`, masking off any bits beyond the lower 11 bits of `identifier`.
+    ///
+    /// This is infallible, and so is useful for conversions that are known to produce valid
+    /// identifiers without having to handle a failure case that can never occur.
+    #[inline]
+    pub(crate) const fn new_masked(identifier: u16) -> Self {
+        Self {
+            identifier: identifier & Self::MAX.identifier,
+            flags: IdentifierFlags::empty(),
+        }
+    }
+
     /// Creates a `StandardId` with additional flags.
     ///
     /// Returns `None` if `identifier` is greater than [`MAX`][Self::MAX].
@@ -70,6 +98,48 @@ impl StandardId {
         self.flags
     }
 
+    /// Compares this identifier to `other`, in a `const` context.
+    ///
+    /// This agrees with the [`PartialOrd`] implementation, comparing the identifiers first and then
+    /// their flags.  The comparison methods of [`Ordering`][cmp::Ordering], such as
+    /// [`is_lt`][cmp::Ordering::is_lt], are also `const`, allowing range checks in `const` code.
+    pub const fn const_cmp(&self, other: &Self) -> cmp::Ordering {
+        match cmp_raw(self.identifier as u32, other.identifier as u32) {
+            cmp::Ordering::Equal => cmp_raw(self.flags.bits(), other.flags.bits()),
+            ordering => ordering,
+        }
+    }
+
+    /// Adds `n` to this identifier, keeping its flags.
+    ///
+    /// # Errors
+    ///
+    /// If the result would be greater than [`MAX`][Self::MAX], `None` is returned.
+    pub const fn checked_add(self, n: u16) -> Option<Self> {
+        match self.identifier.checked_add(n) {
+            Some(identifier) if identifier <= Self::MAX.identifier => Some(Self {
+                identifier,
+                flags: self.flags,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Subtracts `n` from this identifier, keeping its flags.
+    ///
+    /// # Errors
+    ///
+    /// If the result would be less than [`ZERO`][Self::ZERO], `None` is returned.
+    pub const fn checked_sub(self, n: u16) -> Option<Self> {
+        match self.identifier.checked_sub(n) {
+            Some(identifier) => Some(Self {
+                identifier,
+                flags: self.flags,
+            }),
+            None => None,
+        }
+    }
+
     /// Creates a new `StandardId` after setting its flags to a new value.
     #[inline]
     pub const fn set_flags(self, flags: IdentifierFlags) -> Self {
@@ -103,12 +173,31 @@ impl StandardId {
 
 impl fmt::Display for StandardId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let flags = if self.flags.is_empty() {
-            String::new()
-        } else {
-            format!("({:?})", self.flags)
-        };
-        write!(f, "{:#X}{}", self.identifier, flags)
+        write!(f, "{:#X}", self.identifier)?;
+        if !self.flags.is_empty() {
+            write!(f, "({:?})", self.flags)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for StandardId {
+    /// Formats the identifier in hexadecimal, followed by its flags, if any are set.
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=u16:#X}", self.identifier);
+        if !self.flags.is_empty() {
+            defmt::write!(f, "({})", self.flags);
+        }
+    }
+}
+
+impl Default for StandardId {
+    /// Returns [`StandardId::ZERO`], which is the highest priority standard identifier.
+    fn default() -> Self {
+        Self::ZERO
     }
 }
 
@@ -139,6 +228,19 @@ impl ExtendedId {
         flags: IdentifierFlags::EXTENDED,
     };
 
+    /// Minimum value for an extended identifier.
+    ///
+    /// This is an alias of [`ZERO`][Self::ZERO], for code that expects integer-like types to
+    /// define a `MIN`.  As with `ZERO`, this is the highest priority extended identifier.
+    pub const MIN: Self = Self::ZERO;
+
+    /// Range of all valid extended identifiers, from [`MIN`][Self::MIN] to [`MAX`][Self::MAX],
+    /// inclusive.
+    ///
+    /// Identifiers are ordered by their flags after their raw value, so an identifier at the upper
+    /// bound with the remote or error flags set falls outside of this range.
+    pub const RANGE: RangeInclusive<Self> = RangeInclusive::new(Self::MIN, Self::MAX);
+
     /// Creates an `ExtendedId`.
     ///
     /// Returns `None` if `identifier` is greater than [`MAX`][Self::MAX].
@@ -154,6 +256,18 @@ impl ExtendedId {
         }
     }
 
+    /// Creates an `ExtendedId`, masking off any bits beyond the lower 29 bits of `identifier`.
+    ///
+    /// This is infallible, and so is useful for conversions that are known to produce valid
+    /// identifiers without having to handle a failure case that can never occur.
+    #[inline]
+    pub(crate) const fn new_masked(identifier: u32) -> Self {
+        Self {
+            identifier: identifier & Self::MAX.identifier,
+            flags: IdentifierFlags::EXTENDED,
+        }
+    }
+
     /// Creates an `ExtendedId` with additional flags.
     ///
     /// Returns `None` if `identifier` is greater than [`MAX`][Self::MAX].
@@ -181,6 +295,48 @@ impl ExtendedId {
         self.flags
     }
 
+    /// Compares this identifier to `other`, in a `const` context.
+    ///
+    /// This agrees with the [`PartialOrd`] implementation, comparing the identifiers first and then
+    /// their flags.  The comparison methods of [`Ordering`][cmp::Ordering], such as
+    /// [`is_lt`][cmp::Ordering::is_lt], are also `const`, allowing range checks in `const` code.
+    pub const fn const_cmp(&self, other: &Self) -> cmp::Ordering {
+        match cmp_raw(self.identifier, other.identifier) {
+            cmp::Ordering::Equal => cmp_raw(self.flags.bits(), other.flags.bits()),
+            ordering => ordering,
+        }
+    }
+
+    /// Adds `n` to this identifier, keeping its flags.
+    ///
+    /// # Errors
+    ///
+    /// If the result would be greater than [`MAX`][Self::MAX], `None` is returned.
+    pub const fn checked_add(self, n: u32) -> Option<Self> {
+        match self.identifier.checked_add(n) {
+            Some(identifier) if identifier <= Self::MAX.identifier => Some(Self {
+                identifier,
+                flags: self.flags,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Subtracts `n` from this identifier, keeping its flags.
+    ///
+    /// # Errors
+    ///
+    /// If the result would be less than [`ZERO`][Self::ZERO], `None` is returned.
+    pub const fn checked_sub(self, n: u32) -> Option<Self> {
+        match self.identifier.checked_sub(n) {
+            Some(identifier) => Some(Self {
+                identifier,
+                flags: self.flags,
+            }),
+            None => None,
+        }
+    }
+
     /// Creates a new `ExtendedId` after setting its flags to a new value.
     #[inline]
     pub const fn set_flags(self, flags: IdentifierFlags) -> Self {
@@ -203,22 +359,104 @@ impl ExtendedId {
     }
 
     /// Returns the base (standard) portion of this extended identifier.
+    ///
+    /// In CAN 2.0B, an extended identifier is transmitted as an 11-bit "base identifier", followed
+    /// by an 18-bit "identifier extension", such that the base identifier occupies the same
+    /// position in the arbitration field as a standard identifier.  This returns the base
+    /// identifier, which is the upper 11 bits of the 29-bit identifier, i.e. bits 18 to 28.  For
+    /// the lower 11 bits instead, see [`low_standard_id`][Self::low_standard_id].
+    ///
+    /// The remote and error flags are kept, while the extended flag is removed.
     pub const fn as_standard_id(&self) -> StandardId {
         StandardId {
             identifier: (self.identifier >> 18) as u16,
             flags: self.flags.difference(IdentifierFlags::EXTENDED),
         }
     }
+
+    /// Returns the lower 11 bits of this extended identifier as a standard identifier.
+    ///
+    /// Unlike [`as_standard_id`][Self::as_standard_id], which returns the base identifier, this
+    /// returns bits 0 to 10 of the 29-bit identifier, which are the lower 11 bits of the 18-bit
+    /// identifier extension.  This has no meaning on the bus, but is useful for higher-level
+    /// protocols that carry an 11-bit value in the lower bits of an extended identifier.
+    ///
+    /// The remote and error flags are kept, while the extended flag is removed.
+    pub const fn low_standard_id(&self) -> StandardId {
+        StandardId {
+            identifier: (self.identifier & SFF_MASK) as u16,
+            flags: self.flags.difference(IdentifierFlags::EXTENDED),
+        }
+    }
+
+    /// Splits this identifier into the portions above and below the given bit.
+    ///
+    /// The high portion is made up of the bits at and above `bit`, shifted down, and the low
+    /// portion is made up of the bits below `bit`.  Splitting at bit 18 gives the base identifier,
+    /// as returned by [`as_standard_id`][Self::as_standard_id], and the 18-bit identifier extension.
+    ///
+    /// If `bit` is 29 or greater, the high portion is zero and the low portion is the entire
+    /// identifier.
+    pub const fn split_at(&self, bit: u32) -> (u32, u32) {
+        let bit = if bit > EXTENDED_ID_BITS {
+            EXTENDED_ID_BITS
+        } else {
+            bit
+        };
+
+        (self.identifier >> bit, self.identifier & ((1 << bit) - 1))
+    }
+
+    /// Creates an `ExtendedId` from the portions above and below the given bit.
+    ///
+    /// This is the inverse of [`split_at`][Self::split_at]: `high` is shifted up to start at `bit`,
+    /// and combined with `low`.
+    ///
+    /// # Errors
+    ///
+    /// If `bit` is greater than 29, `low` does not fit in the bits below `bit`, or the combined
+    /// identifier is greater than [`MAX`][Self::MAX], `None` is returned.
+    pub const fn from_parts(high: u32, low: u32, bit: u32) -> Option<Self> {
+        if bit > EXTENDED_ID_BITS || low >> bit != 0 {
+            return None;
+        }
+
+        let identifier = (high as u64) << bit | low as u64;
+        if identifier > Self::MAX.identifier as u64 {
+            return None;
+        }
+
+        Self::new(identifier as u32)
+    }
 }
 
 impl fmt::Display for ExtendedId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let flags = if self.flags.is_empty() {
-            String::new()
-        } else {
-            format!("({:?})", self.flags)
-        };
-        write!(f, "{:#X}{}", self.identifier, flags)
+        write!(f, "{:#X}", self.identifier)?;
+        if !self.flags.is_empty() {
+            write!(f, "({:?})", self.flags)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for ExtendedId {
+    /// Formats the identifier in hexadecimal, followed by its flags, if any are set.
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{=u32:#X}", self.identifier);
+        if !self.flags.is_empty() {
+            defmt::write!(f, "({})", self.flags);
+        }
+    }
+}
+
+impl Default for ExtendedId {
+    /// Returns [`ExtendedId::ZERO`], which is the highest priority extended identifier.
+    fn default() -> Self {
+        Self::ZERO
     }
 }
 
@@ -239,6 +477,16 @@ impl fmt::Display for ExtendedId {
 /// `ExtendedId` as the "Identifier Extension (IDE)" bit will be recessive (1) in the case of an
 /// extended identifier, and so the sorting behavior for `StandardId`, `ExtendedId`, and `Id` all
 /// reflect this.
+///
+/// ## Equality and hashing
+///
+/// A standard identifier and an extended identifier are never equal, even if they have the same
+/// value, as they are distinct identifiers on the bus.  Likewise, the addressing mode is part of
+/// the hash of an identifier, which makes `Id` suitable as a key in hash-based collections, such
+/// as dispatch tables, that handle both standard and extended identifiers.
+///
+/// Flags are also considered, so identifiers with the same value and addressing mode but different
+/// flags, such as a data frame identifier and a remote frame identifier, are not equal.
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 pub enum Id {
     /// Standard (11-bit) CAN identifier.
@@ -257,6 +505,33 @@ impl Id {
         }
     }
 
+    /// Creates an `Id` from a raw identifier with the flags encoded in the upper bits.
+    ///
+    /// This is the format used by SocketCAN for `can_id`: if `CAN_EFF_FLAG` is set, an extended
+    /// identifier is created from the lower 29 bits, otherwise a standard identifier is created from
+    /// the lower 11 bits.  Any other bits in the address portion are ignored.  The remaining flags
+    /// are attached to the identifier.
+    ///
+    /// This is the inverse of [`to_raw_with_flags`][Self::to_raw_with_flags].
+    pub const fn from_raw_with_flags(raw: u32) -> Id {
+        let flags = IdentifierFlags::from_bits_truncate(raw);
+        if flags.contains(IdentifierFlags::EXTENDED) {
+            Self::Extended(ExtendedId::new_masked(raw & EFF_MASK).set_flags(flags))
+        } else {
+            Self::Standard(StandardId::new_masked((raw & SFF_MASK) as u16).set_flags(flags))
+        }
+    }
+
+    /// Returns the identifier as a raw integer with the flags encoded in the upper bits.
+    ///
+    /// This is the format used by SocketCAN for `can_id`, where `CAN_EFF_FLAG` is set for extended
+    /// identifiers.
+    ///
+    /// This is the inverse of [`from_raw_with_flags`][Self::from_raw_with_flags].
+    pub const fn to_raw_with_flags(&self) -> u32 {
+        self.as_raw() | self.flags().bits()
+    }
+
     /// Returns the flags set for this identifier.
     pub const fn flags(&self) -> IdentifierFlags {
         match self {
@@ -265,6 +540,20 @@ impl Id {
         }
     }
 
+    /// Compares this identifier to `other`, in a `const` context.
+    ///
+    /// This agrees with the [`PartialOrd`] implementation: standard identifiers always sort before
+    /// extended identifiers, and identifiers with the same addressing mode are compared with
+    /// [`StandardId::const_cmp`] or [`ExtendedId::const_cmp`].
+    pub const fn const_cmp(&self, other: &Self) -> cmp::Ordering {
+        match (self, other) {
+            (Self::Standard(s1), Self::Standard(s2)) => s1.const_cmp(s2),
+            (Self::Standard(_), Self::Extended(_)) => cmp::Ordering::Less,
+            (Self::Extended(_), Self::Standard(_)) => cmp::Ordering::Greater,
+            (Self::Extended(e1), Self::Extended(e2)) => e1.const_cmp(e2),
+        }
+    }
+
     /// Creates a new `Id` after setting its flags to a new value.
     #[inline]
     pub const fn set_flags(self, flags: IdentifierFlags) -> Self {
@@ -285,6 +574,118 @@ impl Id {
             Self::Extended(id) => Self::Extended(id.map_flags(f)),
         }
     }
+
+    /// Creates a normalized version of this `Id`.
+    ///
+    /// The flags of a normalized identifier always agree with its addressing mode: the extended
+    /// flag is set for extended identifiers, and unset for standard identifiers.  While the
+    /// constructors of `Id` uphold this, setting the flags of a [`StandardId`] directly can leave
+    /// the extended flag set, such that two identifiers that refer to the same standard
+    /// identifier, and the same frame type, are not equal.  Normalizing both identifiers before
+    /// comparing them avoids this.
+    pub const fn normalized(&self) -> Id {
+        match self {
+            Self::Standard(id) => {
+                Self::Standard(id.set_flags(id.flags().difference(IdentifierFlags::EXTENDED)))
+            }
+            Self::Extended(id) => Self::Extended(id.set_flags(id.flags())),
+        }
+    }
+
+    /// Creates a version of this `Id` with the remote and error flags cleared.
+    ///
+    /// The addressing mode is kept, and the identifier is [normalized][Self::normalized], so the
+    /// result only has the extended flag set, and only for extended identifiers.
+    pub const fn strip_flags(&self) -> Id {
+        let id = self.normalized();
+        id.set_flags(id.flags().addressing())
+    }
+
+    /// Checks if this identifier is between `start` and `end`, inclusive.
+    ///
+    /// Only the identifier values themselves are compared, and the flags of all identifiers are
+    /// ignored.  If `start` is greater than `end`, they are swapped.
+    ///
+    /// Identifiers can only be in range of identifiers with the same addressing mode, so if this
+    /// identifier's addressing mode is not the same as both `start` and `end`, `false` is returned.
+    pub const fn in_range(&self, start: Id, end: Id) -> bool {
+        let same_mode = matches!(
+            (self, start, end),
+            (Self::Standard(_), Self::Standard(_), Self::Standard(_))
+                | (Self::Extended(_), Self::Extended(_), Self::Extended(_))
+        );
+
+        let (low, high) = if start.as_raw() > end.as_raw() {
+            (end.as_raw(), start.as_raw())
+        } else {
+            (start.as_raw(), end.as_raw())
+        };
+
+        same_mode && self.as_raw() >= low && self.as_raw() <= high
+    }
+
+    /// Checks if this identifier would win arbitration over `other`.
+    ///
+    /// When multiple nodes start transmitting simultaneously, the arbitration fields of their
+    /// frames are compared bit by bit, where a dominant (0) bit overwrites a recessive (1) bit, and
+    /// any node that sends a recessive bit but sees a dominant bit stops transmitting.  The
+    /// arbitration field consists of:
+    ///
+    /// - for standard identifiers: the 11 identifier bits, and then the "Remote Transmission
+    ///   Request (RTR)" bit
+    /// - for extended identifiers: the upper 11 identifier bits, the "Substitute Remote Request
+    ///   (SRR)" bit, which is always recessive, the "Identifier Extension (IDE)" bit, which is
+    ///   recessive for extended identifiers, the lower 18 identifier bits, and then the RTR bit
+    ///
+    /// As such, lower identifiers win over higher identifiers, and data frames win over remote
+    /// frames with the same identifier.  When a standard identifier and an extended identifier
+    /// share the same upper 11 bits, the standard identifier always wins: either its RTR bit is
+    /// dominant while the SRR bit is recessive, or, for a remote frame, its IDE bit is dominant
+    /// while the IDE bit of the extended identifier is recessive.
+    ///
+    /// If both identifiers have identical arbitration fields, neither wins, and `false` is
+    /// returned.  The error flag is not part of the arbitration field, and is ignored.
+    pub const fn wins_arbitration_over(&self, other: &Id) -> bool {
+        self.arbitration_bits() < other.arbitration_bits()
+    }
+
+    /// Gets the priority class of this identifier.
+    ///
+    /// Priority classes are coarse bands that identifiers can be bucketed into, such that a
+    /// scheduler can order pending frames cheaply, without comparing full identifiers.  There are
+    /// eight classes, numbered 0 through 7, where a lower class is a higher priority.
+    ///
+    /// The class is selected by the upper three bits of the base identifier, which is the first
+    /// part of the arbitration field: the 11 identifier bits of a standard identifier, or the upper
+    /// 11 of the 29 identifier bits of an extended identifier.  As such, 0x000-0x0FF is class 0,
+    /// 0x100-0x1FF is class 1, and so on for standard identifiers, while 0x00000000-0x03FFFFFF is
+    /// class 0, 0x04000000-0x07FFFFFF is class 1, and so on for extended identifiers.
+    ///
+    /// Since arbitration is decided by the base identifier first, if one identifier
+    /// [wins arbitration][Self::wins_arbitration_over] over another, its priority class is never
+    /// greater.  For example, the extended identifier 0x00000001 is in class 0, and wins
+    /// arbitration over the standard identifier 0x7FF in class 7.  Flags are ignored.
+    pub const fn priority_class(&self) -> u8 {
+        // The base identifier occupies the upper 11 of the 32 arbitration bits.
+        (self.arbitration_bits() >> 29) as u8
+    }
+
+    // Gets the arbitration field as it would be transmitted, left-aligned in all 32 bits, such that
+    // a lower value wins arbitration.  Bits beyond the end of a standard arbitration field are dominant, as they
+    // are never compared: arbitration is always decided by the IDE bit when comparing a standard
+    // identifier to an extended identifier.
+    pub(crate) const fn arbitration_bits(&self) -> u32 {
+        let rtr = self.flags().contains(IdentifierFlags::REMOTE) as u32;
+        match self {
+            Self::Standard(id) => (id.as_raw() as u32) << 21 | rtr << 20,
+            Self::Extended(id) => {
+                let raw = id.as_raw();
+                let base = raw >> 18;
+                let extension = raw & 0x3FFFF;
+                base << 21 | 1 << 20 | 1 << 19 | extension << 1 | rtr
+            }
+        }
+    }
 }
 
 impl PartialOrd for Id {
@@ -307,6 +708,25 @@ impl fmt::Display for Id {
     }
 }
 
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for Id {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self {
+            Self::Standard(sid) => sid.format(f),
+            Self::Extended(eid) => eid.format(f),
+        }
+    }
+}
+
+impl Default for Id {
+    /// Returns [`StandardId::ZERO`] as a standard identifier, which is the highest priority
+    /// identifier overall.
+    fn default() -> Self {
+        Self::Standard(StandardId::ZERO)
+    }
+}
+
 impl From<StandardId> for Id {
     #[inline]
     fn from(id: StandardId) -> Self {
@@ -321,39 +741,102 @@ impl From<ExtendedId> for Id {
     }
 }
 
-#[cfg(feature = "embedded-can-compat")]
-#[cfg_attr(docsrs, doc(cfg(feature = "embedded-can-compat")))]
-impl Into<embedded_can::StandardId> for StandardId {
-    fn into(self) -> embedded_can::StandardId {
-        unsafe { embedded_can::StandardId::new_unchecked(self.identifier) }
+impl TryFrom<Id> for StandardId {
+    type Error = AddressingMismatch;
+
+    /// Extracts the standard identifier from an [`Id`].
+    ///
+    /// # Errors
+    ///
+    /// If the identifier is an extended identifier, an error is returned.
+    fn try_from(id: Id) -> Result<Self, Self::Error> {
+        match id {
+            Id::Standard(sid) => Ok(sid),
+            Id::Extended(_) => Err(AddressingMismatch { id }),
+        }
+    }
+}
+
+impl TryFrom<Id> for ExtendedId {
+    type Error = AddressingMismatch;
+
+    /// Extracts the extended identifier from an [`Id`].
+    ///
+    /// # Errors
+    ///
+    /// If the identifier is a standard identifier, an error is returned.
+    fn try_from(id: Id) -> Result<Self, Self::Error> {
+        match id {
+            Id::Extended(eid) => Ok(eid),
+            Id::Standard(_) => Err(AddressingMismatch { id }),
+        }
     }
 }
 
-#[cfg(feature = "embedded-can-compat")]
-#[cfg_attr(docsrs, doc(cfg(feature = "embedded-can-compat")))]
-impl Into<embedded_can::ExtendedId> for ExtendedId {
-    fn into(self) -> embedded_can::ExtendedId {
-        unsafe { embedded_can::ExtendedId::new_unchecked(self.identifier) }
+/// Error returned when an [`Id`] does not have the expected addressing mode.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AddressingMismatch {
+    id: Id,
+}
+
+impl AddressingMismatch {
+    /// Gets the identifier that had the unexpected addressing mode.
+    pub const fn id(&self) -> Id {
+        self.id
     }
 }
 
-#[cfg(feature = "embedded-can-compat")]
-#[cfg_attr(docsrs, doc(cfg(feature = "embedded-can-compat")))]
-impl Into<embedded_can::Id> for Id {
-    fn into(self) -> embedded_can::Id {
-        match self {
-            Self::Standard(sid) => embedded_can::Id::Standard(sid.into()),
-            Self::Extended(eid) => embedded_can::Id::Extended(eid.into()),
+impl fmt::Display for AddressingMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.id {
+            Id::Standard(_) => write!(
+                f,
+                "expected an extended identifier, got standard identifier {:#X}",
+                self.id.as_raw()
+            ),
+            Id::Extended(_) => write!(
+                f,
+                "expected a standard identifier, got extended identifier {:#X}",
+                self.id.as_raw()
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for AddressingMismatch {}
+
+// Compares two raw values in a `const` context.
+const fn cmp_raw(a: u32, b: u32) -> cmp::Ordering {
+    if a < b {
+        cmp::Ordering::Less
+    } else if a > b {
+        cmp::Ordering::Greater
+    } else {
+        cmp::Ordering::Equal
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::constants::tests::arb_identifier_flags;
+    use std::{cmp, string::ToString};
+
+    use crate::constants::{
+        tests::arb_identifier_flags, IdentifierFlags, EFF_FLAG, EFF_MASK, ERR_FLAG, RTR_FLAG,
+        SFF_MASK,
+    };
 
     use super::{ExtendedId, Id, StandardId};
-    use proptest::{prop_oneof, strategy::Strategy};
+    use proptest::{arbitrary::any, prop_assert_eq, prop_oneof, proptest, strategy::Strategy};
+
+    fn sid(id: u16) -> Id {
+        Id::Standard(StandardId::new(id).unwrap())
+    }
+
+    fn eid(id: u32) -> Id {
+        Id::Extended(ExtendedId::new(id).unwrap())
+    }
 
     const STANDARD_ID_MIN: u16 = StandardId::ZERO.as_raw();
     const STANDARD_ID_MAX: u16 = StandardId::MAX.as_raw();
@@ -380,4 +863,358 @@ pub(crate) mod tests {
             arb_extendedid().prop_map(Id::from).boxed(),
         ]
     }
+
+    #[test]
+    fn in_range() {
+        assert!(sid(0x7E8).in_range(sid(0x7E0), sid(0x7EF)));
+        assert!(sid(0x7E0).in_range(sid(0x7E0), sid(0x7EF)));
+        assert!(sid(0x7EF).in_range(sid(0x7E0), sid(0x7EF)));
+        assert!(sid(0x7E8).in_range(sid(0x7EF), sid(0x7E0)));
+        assert!(!sid(0x7DF).in_range(sid(0x7E0), sid(0x7EF)));
+        assert!(!sid(0x7F0).in_range(sid(0x7E0), sid(0x7EF)));
+
+        assert!(eid(0x7E8).in_range(eid(0x7E0), eid(0x7EF)));
+        assert!(!eid(0x7E8).in_range(sid(0x7E0), sid(0x7EF)));
+        assert!(!sid(0x7E8).in_range(eid(0x7E0), eid(0x7EF)));
+        assert!(!sid(0x7E8).in_range(sid(0x7E0), eid(0x7EF)));
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(StandardId::ZERO, StandardId::default());
+        assert_eq!(ExtendedId::ZERO, ExtendedId::default());
+        assert_eq!(Id::Standard(StandardId::ZERO), Id::default());
+        assert!(ExtendedId::default()
+            .flags()
+            .contains(IdentifierFlags::EXTENDED));
+    }
+
+    #[test]
+    fn min_and_range() {
+        assert_eq!(StandardId::ZERO, StandardId::MIN);
+        assert_eq!(ExtendedId::ZERO, ExtendedId::MIN);
+
+        assert_eq!(&StandardId::MIN, StandardId::RANGE.start());
+        assert_eq!(&StandardId::MAX, StandardId::RANGE.end());
+        assert!(StandardId::RANGE.contains(&StandardId::new(0x7DF).unwrap()));
+        assert_eq!(&ExtendedId::MIN, ExtendedId::RANGE.start());
+        assert_eq!(&ExtendedId::MAX, ExtendedId::RANGE.end());
+        assert!(ExtendedId::RANGE.contains(&ExtendedId::new(0x18DAF110).unwrap()));
+
+        let remote = StandardId::with_flags(0x7FF, IdentifierFlags::REMOTE).unwrap();
+        assert!(!StandardId::RANGE.contains(&remote));
+    }
+
+    #[test]
+    fn standard_and_extended_are_distinct() {
+        use std::{
+            collections::{hash_map::DefaultHasher, HashMap},
+            hash::{Hash, Hasher},
+        };
+
+        fn hash_of(id: &Id) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            id.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let standard = sid(0x123);
+        let extended = eid(0x123);
+        assert_ne!(standard, extended);
+        assert_ne!(hash_of(&standard), hash_of(&extended));
+
+        let mut table = HashMap::new();
+        table.insert(standard, "standard");
+        table.insert(extended, "extended");
+        assert_eq!(2, table.len());
+        assert_eq!(Some(&"standard"), table.get(&sid(0x123)));
+        assert_eq!(Some(&"extended"), table.get(&eid(0x123)));
+    }
+
+    #[test]
+    fn wins_arbitration_over() {
+        let remote = |id: Id| id.set_flags(id.flags() | IdentifierFlags::REMOTE);
+
+        // Lower identifiers win.
+        assert!(sid(0x100).wins_arbitration_over(&sid(0x101)));
+        assert!(!sid(0x101).wins_arbitration_over(&sid(0x100)));
+        assert!(eid(0x100).wins_arbitration_over(&eid(0x101)));
+
+        // Identical arbitration fields mean neither wins.
+        assert!(!sid(0x100).wins_arbitration_over(&sid(0x100)));
+        assert!(!eid(0x100).wins_arbitration_over(&eid(0x100)));
+
+        // Data frames win over remote frames.
+        assert!(sid(0x100).wins_arbitration_over(&remote(sid(0x100))));
+        assert!(!remote(sid(0x100)).wins_arbitration_over(&sid(0x100)));
+        assert!(eid(0x100).wins_arbitration_over(&remote(eid(0x100))));
+
+        // Standard identifiers win over extended identifiers with the same base identifier,
+        // whether the standard frame is a data frame or remote frame.
+        let base = 0x123;
+        let extended = eid(base << 18);
+        assert!(sid(base as u16).wins_arbitration_over(&extended));
+        assert!(remote(sid(base as u16)).wins_arbitration_over(&extended));
+        assert!(!extended.wins_arbitration_over(&sid(base as u16)));
+        assert!(!extended.wins_arbitration_over(&remote(sid(base as u16))));
+
+        // But extended identifiers with a lower base identifier still win.
+        assert!(eid(0x122 << 18 | 0x3FFFF).wins_arbitration_over(&sid(0x123)));
+        assert!(!sid(0x123).wins_arbitration_over(&eid(0x122 << 18 | 0x3FFFF)));
+
+        // Every bit of the 18-bit extension takes part in arbitration.
+        assert!(eid(0x1FFFF).wins_arbitration_over(&eid(0x20000)));
+        assert!(!eid(0x20000).wins_arbitration_over(&eid(0x1FFFF)));
+        assert!(eid(0x0).wins_arbitration_over(&eid(0x20000)));
+    }
+
+    proptest! {
+        #[test]
+        fn extended_arbitration_follows_raw(a in arb_extendedid(), b in arb_extendedid()) {
+            // Only data frames, so that the RTR bit does not take part.
+            let (a, b) = (Id::Extended(a).strip_flags(), Id::Extended(b).strip_flags());
+            prop_assert_eq!(a.as_raw() < b.as_raw(), a.wins_arbitration_over(&b));
+        }
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let sid = StandardId::new(0x7E0).unwrap();
+        assert_eq!(StandardId::new(0x7E8), sid.checked_add(8));
+        assert_eq!(Some(StandardId::MAX), StandardId::ZERO.checked_add(0x7FF));
+        assert_eq!(None, StandardId::MAX.checked_add(1));
+        assert_eq!(None, StandardId::ZERO.checked_add(u16::MAX));
+        assert_eq!(StandardId::new(0x7D8), sid.checked_sub(8));
+        assert_eq!(Some(StandardId::ZERO), StandardId::MAX.checked_sub(0x7FF));
+        assert_eq!(None, StandardId::ZERO.checked_sub(1));
+
+        let eid = ExtendedId::new(0x18DAF100).unwrap();
+        assert_eq!(ExtendedId::new(0x18DAF110), eid.checked_add(0x10));
+        assert_eq!(
+            Some(ExtendedId::MAX),
+            ExtendedId::ZERO.checked_add(0x1FFF_FFFF)
+        );
+        assert_eq!(None, ExtendedId::MAX.checked_add(1));
+        assert_eq!(None, ExtendedId::MAX.checked_add(u32::MAX));
+        assert_eq!(
+            Some(ExtendedId::ZERO),
+            ExtendedId::MAX.checked_sub(0x1FFF_FFFF)
+        );
+        assert_eq!(None, ExtendedId::ZERO.checked_sub(1));
+
+        let remote = StandardId::with_flags(0x100, IdentifierFlags::REMOTE).unwrap();
+        let next = remote.checked_add(1).unwrap();
+        assert_eq!(0x101, next.as_raw());
+        assert_eq!(IdentifierFlags::REMOTE, next.flags());
+    }
+
+    #[test]
+    fn split_at() {
+        let eid = ExtendedId::new(0x18DAF110).unwrap();
+
+        let (base, extension) = eid.split_at(18);
+        assert_eq!(u32::from(eid.as_standard_id().as_raw()), base);
+        assert_eq!(0x2F110, extension);
+        assert_eq!(Some(eid), ExtendedId::from_parts(base, extension, 18));
+
+        assert_eq!((0x18DAF1, 0x10), eid.split_at(8));
+        assert_eq!((0x18DAF110, 0), eid.split_at(0));
+        assert_eq!((0, 0x18DAF110), eid.split_at(29));
+        assert_eq!((0, 0x18DAF110), eid.split_at(32));
+        assert_eq!((0, 0x18DAF110), eid.split_at(u32::MAX));
+
+        assert_eq!(Some(eid), ExtendedId::from_parts(0x18DAF1, 0x10, 8));
+        assert_eq!(Some(eid), ExtendedId::from_parts(0, 0x18DAF110, 29));
+        assert_eq!(
+            Some(ExtendedId::MAX),
+            ExtendedId::from_parts(0x7FF, 0x3FFFF, 18)
+        );
+
+        // The low portion must fit below the split, and the result must fit in 29 bits.
+        assert_eq!(None, ExtendedId::from_parts(0x18DAF1, 0x100, 8));
+        assert_eq!(None, ExtendedId::from_parts(0x800, 0, 18));
+        assert_eq!(None, ExtendedId::from_parts(1, 0, 29));
+        assert_eq!(None, ExtendedId::from_parts(0, 0x2000_0000, 29));
+        assert_eq!(None, ExtendedId::from_parts(0, 0, 30));
+    }
+
+    #[test]
+    fn normalized() {
+        let folded = Id::Standard(
+            StandardId::new(0x123)
+                .unwrap()
+                .set_flags(IdentifierFlags::EXTENDED),
+        );
+        let plain = sid(0x123);
+        assert_ne!(folded, plain);
+        assert_eq!(plain, folded.normalized());
+        assert_eq!(plain.normalized(), folded.normalized());
+
+        let remote = eid(0x18DAF110).set_flags(IdentifierFlags::REMOTE);
+        assert_eq!(remote, remote.normalized());
+        assert_eq!(
+            IdentifierFlags::EXTENDED | IdentifierFlags::REMOTE,
+            remote.normalized().flags()
+        );
+    }
+
+    #[test]
+    fn strip_flags() {
+        let all = IdentifierFlags::all();
+        let standard = Id::Standard(StandardId::new(0x7E8).unwrap().set_flags(all));
+        assert_eq!(sid(0x7E8), standard.strip_flags());
+        assert_eq!(IdentifierFlags::empty(), standard.strip_flags().flags());
+
+        let extended = eid(0x18DAF110).set_flags(all);
+        assert_eq!(eid(0x18DAF110), extended.strip_flags());
+        assert_eq!(IdentifierFlags::EXTENDED, extended.strip_flags().flags());
+    }
+
+    #[test]
+    fn try_from_id() {
+        let standard = sid(0x7E8);
+        let extended = eid(0x18DAF110);
+
+        assert_eq!(
+            Ok(StandardId::new(0x7E8).unwrap()),
+            StandardId::try_from(standard)
+        );
+        assert_eq!(
+            Ok(ExtendedId::new(0x18DAF110).unwrap()),
+            ExtendedId::try_from(extended)
+        );
+
+        let err = StandardId::try_from(extended).unwrap_err();
+        assert_eq!(extended, err.id());
+        assert_eq!(
+            "expected a standard identifier, got extended identifier 0x18DAF110",
+            err.to_string()
+        );
+
+        let err = ExtendedId::try_from(standard).unwrap_err();
+        assert_eq!(standard, err.id());
+        assert_eq!(
+            "expected an extended identifier, got standard identifier 0x7E8",
+            err.to_string()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn normalized_round_trips_raw(id in arb_id()) {
+            prop_assert_eq!(id.normalized(), Id::from_raw_with_flags(id.to_raw_with_flags()));
+            prop_assert_eq!(id.normalized(), id.normalized().normalized());
+        }
+
+        #[test]
+        fn split_at_round_trip(id in arb_extendedid(), bit in 0u32..=29) {
+            let (high, low) = id.split_at(bit);
+            let rebuilt = ExtendedId::from_parts(high, low, bit).unwrap();
+            prop_assert_eq!(id.as_raw(), rebuilt.as_raw());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn raw_with_flags_round_trip(raw in any::<u32>()) {
+            let id = Id::from_raw_with_flags(raw);
+
+            let address_mask = if raw & EFF_FLAG != 0 { EFF_MASK } else { SFF_MASK };
+            let normalized = raw & (address_mask | EFF_FLAG | RTR_FLAG | ERR_FLAG);
+            prop_assert_eq!(normalized, id.to_raw_with_flags());
+            prop_assert_eq!(id, Id::from_raw_with_flags(id.to_raw_with_flags()));
+        }
+
+        #[test]
+        fn to_raw_with_flags_round_trip(id in arb_id()) {
+            prop_assert_eq!(id, Id::from_raw_with_flags(id.to_raw_with_flags()));
+        }
+    }
+
+    #[test]
+    fn priority_class() {
+        assert_eq!(0, sid(0x000).priority_class());
+        assert_eq!(0, sid(0x0FF).priority_class());
+        assert_eq!(1, sid(0x100).priority_class());
+        assert_eq!(7, sid(0x7FF).priority_class());
+        assert_eq!(0, eid(0x000).priority_class());
+        assert_eq!(0, eid(0x001).priority_class());
+        assert_eq!(0, eid(0x03FF_FFFF).priority_class());
+        assert_eq!(1, eid(0x0400_0000).priority_class());
+        assert_eq!(7, eid(0x1FFF_FFFF).priority_class());
+
+        // The extension bits never change the class.
+        assert_eq!(0, eid(0x1FFFF).priority_class());
+        assert_eq!(0, eid(0x20000).priority_class());
+        assert_eq!(0, eid(0x3FFFF).priority_class());
+
+        assert!(eid(0x001).wins_arbitration_over(&sid(0x7FF)));
+        assert!(eid(0x001).priority_class() < sid(0x7FF).priority_class());
+    }
+
+    proptest! {
+        #[test]
+        fn priority_class_follows_arbitration(a in arb_id(), b in arb_id()) {
+            assert!(a.priority_class() < 8);
+            if a.wins_arbitration_over(&b) {
+                assert!(a.priority_class() <= b.priority_class());
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn const_cmp_agrees_with_partial_ord(a in arb_id(), b in arb_id()) {
+            assert!(Some(a.const_cmp(&b)) == a.partial_cmp(&b));
+            if let (Id::Standard(a), Id::Standard(b)) = (a, b) {
+                assert!(Some(a.const_cmp(&b)) == a.partial_cmp(&b));
+            }
+            if let (Id::Extended(a), Id::Extended(b)) = (a, b) {
+                assert!(Some(a.const_cmp(&b)) == a.partial_cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn const_cmp() {
+        const ORDERINGS: [cmp::Ordering; 3] = [
+            StandardId::ZERO.const_cmp(&StandardId::MAX),
+            ExtendedId::MAX.const_cmp(&ExtendedId::ZERO),
+            Id::Standard(StandardId::MAX).const_cmp(&Id::Extended(ExtendedId::ZERO)),
+        ];
+        assert_eq!(
+            [
+                cmp::Ordering::Less,
+                cmp::Ordering::Greater,
+                cmp::Ordering::Less
+            ],
+            ORDERINGS
+        );
+    }
+
+    #[test]
+    fn standard_id_interpretations() {
+        let eid = ExtendedId::new(0x18DAF110).unwrap();
+
+        // Base identifier: bits 18 to 28, as transmitted before the identifier extension.
+        assert_eq!(0x636, eid.as_standard_id().as_raw());
+
+        // Lower 11 bits of the identifier extension.
+        assert_eq!(0x110, eid.low_standard_id().as_raw());
+
+        let remote = eid.set_flags(IdentifierFlags::REMOTE);
+        assert_eq!(IdentifierFlags::REMOTE, remote.as_standard_id().flags());
+        assert_eq!(IdentifierFlags::REMOTE, remote.low_standard_id().flags());
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<StandardId>();
+        assert_format::<ExtendedId>();
+        assert_format::<Id>();
+        assert_format::<IdentifierFlags>();
+    }
 }
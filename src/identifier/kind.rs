@@ -0,0 +1,137 @@
+use core::fmt;
+
+use crate::constants::j1939;
+
+use super::{
+    obd::{DiagnosticBroadcastAddress, DiagnosticRequestAddress, DiagnosticResponseAddress},
+    Id,
+};
+
+/// Coarse classification of an identifier.
+///
+/// See [`Id::classify`] for how identifiers are classified.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IdKind {
+    /// Legislated OBD functional request (broadcast) address.
+    ///
+    /// See [`DiagnosticBroadcastAddress`].
+    ObdBroadcast,
+
+    /// Legislated OBD physical request address.
+    ///
+    /// See [`DiagnosticRequestAddress`].
+    ObdPhysicalRequest,
+
+    /// Legislated OBD physical response address.
+    ///
+    /// See [`DiagnosticResponseAddress`].
+    ObdPhysicalResponse,
+
+    /// J1939 identifier, with the given parameter group number (PGN).
+    J1939 {
+        /// Parameter group number of the identifier.
+        pgn: u32,
+    },
+
+    /// Any other identifier, standard or extended.
+    Other,
+}
+
+impl fmt::Display for IdKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ObdBroadcast => write!(f, "OBD broadcast"),
+            Self::ObdPhysicalRequest => write!(f, "OBD physical request"),
+            Self::ObdPhysicalResponse => write!(f, "OBD physical response"),
+            Self::J1939 { pgn } => write!(f, "J1939 PGN {:#X}", pgn),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+impl Id {
+    /// Classifies this identifier.
+    ///
+    /// Identifiers within the legislated OBD ranges, for either addressing mode, are classified as
+    /// the corresponding OBD address.  Any other extended identifier is classified as a J1939
+    /// identifier if it follows the J1939 layout, i.e. its extended data page (EDP) bit is clear.
+    /// With the EDP bit set, the identifier is either reserved by J1939, or, when the data page
+    /// (DP) bit is also set, defined by ISO 15765-3 rather than J1939.  Such identifiers, and any
+    /// other standard identifier, are classified as [`IdKind::Other`].
+    ///
+    /// The remote and error flags are ignored.
+    pub fn classify(&self) -> IdKind {
+        let id = self.strip_flags();
+        if id == DiagnosticBroadcastAddress::standard().id()
+            || id == DiagnosticBroadcastAddress::extended().id()
+        {
+            IdKind::ObdBroadcast
+        } else if DiagnosticRequestAddress::from_id(id).is_some() {
+            IdKind::ObdPhysicalRequest
+        } else if DiagnosticResponseAddress::from_id(id).is_some() {
+            IdKind::ObdPhysicalResponse
+        } else if let Id::Extended(eid) = id {
+            let fields = j1939::unpack(eid.as_raw());
+            if fields.extended_data_page {
+                IdKind::Other
+            } else {
+                IdKind::J1939 { pgn: fields.pgn() }
+            }
+        } else {
+            IdKind::Other
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use crate::{
+        constants::IdentifierFlags,
+        identifier::{ExtendedId, Id, StandardId},
+    };
+
+    use super::IdKind;
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    fn eid(raw: u32) -> Id {
+        Id::Extended(ExtendedId::new(raw).unwrap())
+    }
+
+    #[test]
+    fn classify() {
+        assert_eq!(IdKind::ObdBroadcast, sid(0x7DF).classify());
+        assert_eq!(IdKind::ObdPhysicalRequest, sid(0x7E0).classify());
+        assert_eq!(IdKind::ObdPhysicalResponse, sid(0x7E8).classify());
+        assert_eq!(IdKind::Other, sid(0x123).classify());
+
+        assert_eq!(IdKind::ObdBroadcast, eid(0x18DB33F1).classify());
+        assert_eq!(IdKind::ObdPhysicalRequest, eid(0x18DA10F1).classify());
+        assert_eq!(IdKind::ObdPhysicalResponse, eid(0x18DAF110).classify());
+        assert_eq!(IdKind::J1939 { pgn: 0xF004 }, eid(0x0CF00400).classify());
+
+        // Extended identifiers with the value of a standard OBD identifier are not OBD identifiers.
+        assert_eq!(IdKind::J1939 { pgn: 0 }, eid(0x7DF).classify());
+
+        // Extended identifiers with the extended data page bit set are not J1939 identifiers.
+        assert_eq!(IdKind::Other, eid(0x1ADA10F1).classify());
+        assert_eq!(IdKind::Other, eid(0x1BDA10F1).classify());
+
+        let remote = sid(0x7DF).set_flags(IdentifierFlags::REMOTE);
+        assert_eq!(IdKind::ObdBroadcast, remote.classify());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("OBD broadcast", IdKind::ObdBroadcast.to_string());
+        assert_eq!(
+            "J1939 PGN 0xF004",
+            IdKind::J1939 { pgn: 0xF004 }.to_string()
+        );
+        assert_eq!("other", IdKind::Other.to_string());
+    }
+}
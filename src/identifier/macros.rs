@@ -0,0 +1,61 @@
+/// Creates a [`StandardId`][crate::identifier::StandardId] at compile time.
+///
+/// The identifier is checked when compiling, so an identifier that is out of range results in a
+/// compile error rather than a panic at runtime.  As such, the identifier must be a constant
+/// expression.
+///
+/// ```
+/// use can::{identifier::StandardId, std_id};
+///
+/// const BROADCAST: StandardId = std_id!(0x7DF);
+/// assert_eq!(0x7DF, BROADCAST.as_raw());
+/// ```
+///
+/// Identifiers larger than 0x7FF fail to compile:
+///
+/// ```compile_fail
+/// use can::std_id;
+///
+/// let id = std_id!(0x800);
+/// ```
+#[macro_export]
+macro_rules! std_id {
+    ($id:expr) => {{
+        const ID: $crate::identifier::StandardId = match $crate::identifier::StandardId::new($id) {
+            ::core::option::Option::Some(id) => id,
+            ::core::option::Option::None => ::core::panic!("standard identifier out of range"),
+        };
+        ID
+    }};
+}
+
+/// Creates an [`ExtendedId`][crate::identifier::ExtendedId] at compile time.
+///
+/// The identifier is checked when compiling, so an identifier that is out of range results in a
+/// compile error rather than a panic at runtime.  As such, the identifier must be a constant
+/// expression.
+///
+/// ```
+/// use can::{ext_id, identifier::ExtendedId};
+///
+/// const BROADCAST: ExtendedId = ext_id!(0x18DB33F1);
+/// assert_eq!(0x18DB33F1, BROADCAST.as_raw());
+/// ```
+///
+/// Identifiers larger than 0x1FFFFFFF fail to compile:
+///
+/// ```compile_fail
+/// use can::ext_id;
+///
+/// let id = ext_id!(0x20000000);
+/// ```
+#[macro_export]
+macro_rules! ext_id {
+    ($id:expr) => {{
+        const ID: $crate::identifier::ExtendedId = match $crate::identifier::ExtendedId::new($id) {
+            ::core::option::Option::Some(id) => id,
+            ::core::option::Option::None => ::core::panic!("extended identifier out of range"),
+        };
+        ID
+    }};
+}
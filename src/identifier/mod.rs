@@ -2,10 +2,21 @@
 //!
 //! Provides various types for constructing CAN identifiers as well as filtering them.
 
+#[macro_use]
+mod macros;
+
 mod id;
 pub use self::id::*;
 
 mod filter;
 pub use self::filter::*;
 
+mod kind;
+pub use self::kind::*;
+
+#[cfg(feature = "alloc")]
+mod filter_set;
+#[cfg(feature = "alloc")]
+pub use self::filter_set::*;
+
 pub mod obd;
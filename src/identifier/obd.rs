@@ -1,20 +1,27 @@
 //! OBD-specific (On-board diagnostics) identifiers, based on ISO 15765-4.
 
-use std::fmt;
-
-use super::{filter::Filter, ExtendedId, Id, StandardId};
-
-const OBD_BROADCAST_ADDR_STANDARD: Id = Id::Standard(standard_id(0x7DF));
-const OBD_BROADCAST_ADDR_EXTENDED: Id = Id::Extended(extended_id(0x18DB33F1));
-const OBD_REQ_ADDR_START_STANDARD: Id = Id::Standard(standard_id(0x7E0));
-const OBD_REQ_ADDR_END_STANDARD: Id = Id::Standard(standard_id(0x7E7));
-const OBD_RESP_ADDR_START_STANDARD: Id = Id::Standard(standard_id(0x7E8));
-const OBD_RESP_ADDR_END_STANDARD: Id = Id::Standard(standard_id(0x7EF));
-const OBD_REQ_ADDR_START_EXTENDED: Id = Id::Extended(extended_id(0x18DA00F1));
-const OBD_REQ_ADDR_END_EXTENDED: Id = Id::Extended(extended_id(0x18DAFFF1));
-const OBD_RESP_ADDR_START_EXTENDED: Id = Id::Extended(extended_id(0x18DAF100));
-const OBD_RESP_ADDR_END_EXTENDED: Id = Id::Extended(extended_id(0x18DAF1FF));
+use core::{fmt, ops::RangeInclusive};
+
+use crate::constants::IdentifierFlags;
+
+use super::{
+    filter::{Filter, Mask},
+    ExtendedId, Id, StandardId,
+};
+
+const OBD_BROADCAST_ADDR_STANDARD: Id = Id::Standard(std_id!(0x7DF));
+const OBD_BROADCAST_ADDR_EXTENDED: Id = Id::Extended(ext_id!(0x18DB33F1));
+const OBD_REQ_ADDR_START_STANDARD: Id = Id::Standard(std_id!(0x7E0));
+const OBD_REQ_ADDR_END_STANDARD: Id = Id::Standard(std_id!(0x7E7));
+const OBD_RESP_ADDR_START_STANDARD: Id = Id::Standard(std_id!(0x7E8));
+const OBD_RESP_ADDR_END_STANDARD: Id = Id::Standard(std_id!(0x7EF));
+const OBD_REQ_ADDR_START_EXTENDED: Id = Id::Extended(ext_id!(0x18DA00F1));
+const OBD_REQ_ADDR_END_EXTENDED: Id = Id::Extended(ext_id!(0x18DAFFF1));
+const OBD_RESP_ADDR_START_EXTENDED: Id = Id::Extended(ext_id!(0x18DAF100));
+const OBD_RESP_ADDR_END_EXTENDED: Id = Id::Extended(ext_id!(0x18DAF1FF));
 const OBD_REQ_RESP_ADDR_OFFSET_STANDARD: u16 = 8;
+const OBD_TESTER_ADDR_EXTENDED: u32 = 0xF1;
+const OBD_REQ_TARGET_ADDR_MASK_EXTENDED: Mask = Mask::new(0xFFFF00FF);
 
 /// Functional request address for legislated OBD diagnostic messages.
 ///
@@ -27,6 +34,12 @@ const OBD_REQ_RESP_ADDR_OFFSET_STANDARD: u16 = 8;
 pub struct DiagnosticBroadcastAddress(Id);
 
 impl DiagnosticBroadcastAddress {
+    /// Identifier of the diagnostic broadcast address for standard addressing, 0x7DF.
+    pub const STANDARD_ID: Id = OBD_BROADCAST_ADDR_STANDARD;
+
+    /// Identifier of the diagnostic broadcast address for extended addressing, 0x18DB33F1.
+    pub const EXTENDED_ID: Id = OBD_BROADCAST_ADDR_EXTENDED;
+
     /// Gets the diagnostic broadcast address for standard addressing.
     ///
     /// Standard addressing refers to the 11-bit addressing mode, also known as CAN 2.0A.
@@ -59,9 +72,9 @@ impl fmt::Display for DiagnosticBroadcastAddress {
     }
 }
 
-impl Into<Id> for DiagnosticBroadcastAddress {
-    fn into(self) -> Id {
-        self.0
+impl From<DiagnosticBroadcastAddress> for Id {
+    fn from(addr: DiagnosticBroadcastAddress) -> Self {
+        addr.0
     }
 }
 
@@ -88,14 +101,31 @@ impl Into<Id> for DiagnosticBroadcastAddress {
 pub struct DiagnosticRequestAddress(Id);
 
 impl DiagnosticRequestAddress {
+    /// Range of physical request identifiers for standard addressing, 0x7E0 to 0x7E7.
+    pub const STANDARD_RANGE: RangeInclusive<Id> =
+        RangeInclusive::new(OBD_REQ_ADDR_START_STANDARD, OBD_REQ_ADDR_END_STANDARD);
+
+    /// Range of physical request identifiers for extended addressing, 0x18DA00F1 to 0x18DAFFF1.
+    ///
+    /// Only identifiers in this range with a source address of 0xF1 are valid physical request
+    /// addresses: see [`from_id`][Self::from_id].
+    pub const EXTENDED_RANGE: RangeInclusive<Id> =
+        RangeInclusive::new(OBD_REQ_ADDR_START_EXTENDED, OBD_REQ_ADDR_END_EXTENDED);
+
     /// Creates a [`DiagnosticRequestAddress`] from the given identifier.
     ///
     /// Depending on the addressing mode of the identifier, a certain range of identifiers are valid
     /// for legislated OBD purposes.  If the given identifier is not within that range, `None` will
     /// be returned.
-    pub fn from_id(id: Id) -> Option<DiagnosticRequestAddress> {
-        let is_standard = id >= OBD_REQ_ADDR_START_STANDARD && id <= OBD_REQ_ADDR_END_STANDARD;
-        let is_extended = id >= OBD_REQ_ADDR_START_EXTENDED && id <= OBD_REQ_ADDR_END_EXTENDED;
+    ///
+    /// For extended addressing, the source address must also be that of the external test
+    /// equipment, 0xF1, such that the identifier can always be paired with a response address.
+    pub const fn from_id(id: Id) -> Option<DiagnosticRequestAddress> {
+        let is_standard = id.const_cmp(&OBD_REQ_ADDR_START_STANDARD).is_ge()
+            && id.const_cmp(&OBD_REQ_ADDR_END_STANDARD).is_le();
+        let is_extended = id.const_cmp(&OBD_REQ_ADDR_START_EXTENDED).is_ge()
+            && id.const_cmp(&OBD_REQ_ADDR_END_EXTENDED).is_le()
+            && id.as_raw() & 0xFF == OBD_TESTER_ADDR_EXTENDED;
 
         if is_standard || is_extended {
             Some(Self(id))
@@ -104,33 +134,96 @@ impl DiagnosticRequestAddress {
         }
     }
 
+    /// Gets all physical request addresses for standard addressing.
+    ///
+    /// Yields identifiers 0x7E0 to 0x7E7, as outlined by ISO 15765-4:2005(E), section 6.3.2.2,
+    /// table 3, "11 bit legislated-OBD CAN identifiers".
+    pub fn all_standard() -> impl Iterator<Item = DiagnosticRequestAddress> {
+        (OBD_REQ_ADDR_START_STANDARD.as_raw()..=OBD_REQ_ADDR_END_STANDARD.as_raw())
+            .map(|raw| Self(Id::Standard(StandardId::new_masked(raw as u16))))
+    }
+
+    /// Gets all physical request addresses for extended addressing.
+    ///
+    /// Yields identifiers 0x18DA00F1 to 0x18DAFFF1, where the target address varies and the source
+    /// address is always 0xF1, as outlined by ISO 15765-4:2005(E), section 6.3.2.3, table 5, "29
+    /// bit legislated-OBD CAN identifiers".
+    pub fn all_extended() -> impl Iterator<Item = DiagnosticRequestAddress> {
+        (0..=u8::MAX).map(|target| {
+            let raw = OBD_REQ_ADDR_START_EXTENDED.as_raw() | u32::from(target) << 8;
+            Self(Id::Extended(ExtendedId::new_masked(raw)))
+        })
+    }
+
     /// Gets the identifier that this request address represents.
     pub fn id(&self) -> Id {
         self.0
     }
 
+    /// Gets the target address of this request address.
+    ///
+    /// For extended addressing, the identifier is made up of a target address, in the second
+    /// byte, and a source address, in the first byte.  For example, the identifier 0x18DAF110 has
+    /// a target address of 0xF1 and a source address of 0x10.
+    ///
+    /// # Errors
+    ///
+    /// If this request address uses standard addressing, `None` is returned.
+    pub const fn target_address(&self) -> Option<u8> {
+        match self.0 {
+            Id::Standard(_) => None,
+            Id::Extended(eid) => Some((eid.as_raw() >> 8) as u8),
+        }
+    }
+
+    /// Gets the source address of this request address.
+    ///
+    /// See [`target_address`][Self::target_address] for more information.
+    ///
+    /// # Errors
+    ///
+    /// If this request address uses standard addressing, `None` is returned.
+    pub const fn source_address(&self) -> Option<u8> {
+        match self.0 {
+            Id::Standard(_) => None,
+            Id::Extended(eid) => Some(eid.as_raw() as u8),
+        }
+    }
+
     /// Creates the reciprocal [`DiagnosticResponseAddress`] to this request addresses.
     ///
     /// See the documentation of [`DiagnosticRequestAddress`] for more information.
     pub fn into_response_address(&self) -> DiagnosticResponseAddress {
+        // As `from_id` only accepts identifiers within the request address ranges, the computed
+        // identifiers always fall within the response address ranges: 0x7E0-0x7E7 maps to
+        // 0x7E8-0x7EF, and 0x18DAXXF1 maps to 0x18DAF1XX.
         match self.0 {
             Id::Standard(sid) => {
                 let raw_offset_id = sid.as_raw() + OBD_REQ_RESP_ADDR_OFFSET_STANDARD;
-                let response_id = StandardId::new(raw_offset_id).unwrap();
+                let response_id = StandardId::new_masked(raw_offset_id);
                 DiagnosticResponseAddress(Id::Standard(response_id))
             }
             Id::Extended(eid) => {
                 let raw_offset_id = swap_eid_target_source(eid.as_raw());
-                let response_id = ExtendedId::new(raw_offset_id).unwrap();
+                let response_id = ExtendedId::new_masked(raw_offset_id);
                 DiagnosticResponseAddress(Id::Extended(response_id))
             }
         }
     }
+
+    /// Checks if the given response address is the reciprocal of this request address.
+    ///
+    /// Any flags set on either identifier are ignored.
+    ///
+    /// See the documentation of [`DiagnosticRequestAddress`] for more information.
+    pub fn pairs_with(&self, response: &DiagnosticResponseAddress) -> bool {
+        self.into_response_address().0 == response.0.set_flags(IdentifierFlags::empty())
+    }
 }
 
-impl Into<Id> for DiagnosticRequestAddress {
-    fn into(self) -> Id {
-        self.0
+impl From<DiagnosticRequestAddress> for Id {
+    fn from(addr: DiagnosticRequestAddress) -> Self {
+        addr.0
     }
 }
 
@@ -163,14 +256,28 @@ impl fmt::Display for DiagnosticRequestAddress {
 pub struct DiagnosticResponseAddress(Id);
 
 impl DiagnosticResponseAddress {
+    /// Range of physical response identifiers for standard addressing, 0x7E8 to 0x7EF.
+    pub const STANDARD_RANGE: RangeInclusive<Id> =
+        RangeInclusive::new(OBD_RESP_ADDR_START_STANDARD, OBD_RESP_ADDR_END_STANDARD);
+
+    /// Range of physical response identifiers for extended addressing, 0x18DAF100 to 0x18DAF1FF.
+    pub const EXTENDED_RANGE: RangeInclusive<Id> =
+        RangeInclusive::new(OBD_RESP_ADDR_START_EXTENDED, OBD_RESP_ADDR_END_EXTENDED);
+
     /// Creates a [`DiagnosticResponseAddress`] from the given identifier.
     ///
     /// Depending on the addressing mode of the identifier, a certain range of identifiers are valid
     /// for legislated OBD purposes.  If the given identifier is not within that range, `None` will
     /// be returned.
-    pub fn from_id(id: Id) -> Option<DiagnosticResponseAddress> {
-        let is_standard = id >= OBD_RESP_ADDR_START_STANDARD && id <= OBD_RESP_ADDR_END_STANDARD;
-        let is_extended = id >= OBD_RESP_ADDR_START_EXTENDED && id <= OBD_RESP_ADDR_END_EXTENDED;
+    ///
+    /// For extended addressing, the range of valid identifiers already constrains the target
+    /// address to be that of the external test equipment, 0xF1, such that the identifier can always
+    /// be paired with a request address.
+    pub const fn from_id(id: Id) -> Option<DiagnosticResponseAddress> {
+        let is_standard = id.const_cmp(&OBD_RESP_ADDR_START_STANDARD).is_ge()
+            && id.const_cmp(&OBD_RESP_ADDR_END_STANDARD).is_le();
+        let is_extended = id.const_cmp(&OBD_RESP_ADDR_START_EXTENDED).is_ge()
+            && id.const_cmp(&OBD_RESP_ADDR_END_EXTENDED).is_le();
 
         if is_standard || is_extended {
             Some(Self(id))
@@ -179,33 +286,96 @@ impl DiagnosticResponseAddress {
         }
     }
 
+    /// Gets all physical response addresses for standard addressing.
+    ///
+    /// Yields identifiers 0x7E8 to 0x7EF, as outlined by ISO 15765-4:2005(E), section 6.3.2.2,
+    /// table 3, "11 bit legislated-OBD CAN identifiers".
+    pub fn all_standard() -> impl Iterator<Item = DiagnosticResponseAddress> {
+        (OBD_RESP_ADDR_START_STANDARD.as_raw()..=OBD_RESP_ADDR_END_STANDARD.as_raw())
+            .map(|raw| Self(Id::Standard(StandardId::new_masked(raw as u16))))
+    }
+
+    /// Gets all physical response addresses for extended addressing.
+    ///
+    /// Yields identifiers 0x18DAF100 to 0x18DAF1FF, where the target address is always 0xF1 and
+    /// the source address varies, as outlined by ISO 15765-4:2005(E), section 6.3.2.3, table 5,
+    /// "29 bit legislated-OBD CAN identifiers".
+    pub fn all_extended() -> impl Iterator<Item = DiagnosticResponseAddress> {
+        (0..=u8::MAX).map(|source| {
+            let raw = OBD_RESP_ADDR_START_EXTENDED.as_raw() | u32::from(source);
+            Self(Id::Extended(ExtendedId::new_masked(raw)))
+        })
+    }
+
     /// Gets the identifier that this response address represents.
     pub fn id(&self) -> Id {
         self.0
     }
 
+    /// Gets the target address of this response address.
+    ///
+    /// For extended addressing, the identifier is made up of a target address, in the second
+    /// byte, and a source address, in the first byte.  For example, the identifier 0x18DAF110 has
+    /// a target address of 0xF1 and a source address of 0x10.
+    ///
+    /// # Errors
+    ///
+    /// If this response address uses standard addressing, `None` is returned.
+    pub const fn target_address(&self) -> Option<u8> {
+        match self.0 {
+            Id::Standard(_) => None,
+            Id::Extended(eid) => Some((eid.as_raw() >> 8) as u8),
+        }
+    }
+
+    /// Gets the source address of this response address.
+    ///
+    /// See [`target_address`][Self::target_address] for more information.
+    ///
+    /// # Errors
+    ///
+    /// If this response address uses standard addressing, `None` is returned.
+    pub const fn source_address(&self) -> Option<u8> {
+        match self.0 {
+            Id::Standard(_) => None,
+            Id::Extended(eid) => Some(eid.as_raw() as u8),
+        }
+    }
+
     /// Creates the reciprocal [`DiagnosticRequestAddress`] to this request addresses.
     ///
     /// See the documentation of [`DiagnosticResponseAddress`] for more information.
     pub fn into_request_address(&self) -> DiagnosticRequestAddress {
+        // As `from_id` only accepts identifiers within the response address ranges, the computed
+        // identifiers always fall within the request address ranges: 0x7E8-0x7EF maps to
+        // 0x7E0-0x7E7, which cannot underflow, and 0x18DAF1XX maps to 0x18DAXXF1.
         match self.0 {
             Id::Standard(sid) => {
                 let raw_offset_id = sid.as_raw() - OBD_REQ_RESP_ADDR_OFFSET_STANDARD;
-                let response_id = StandardId::new(raw_offset_id).unwrap();
-                DiagnosticRequestAddress(Id::Standard(response_id))
+                let request_id = StandardId::new_masked(raw_offset_id);
+                DiagnosticRequestAddress(Id::Standard(request_id))
             }
             Id::Extended(eid) => {
                 let raw_offset_id = swap_eid_target_source(eid.as_raw());
-                let response_id = ExtendedId::new(raw_offset_id).unwrap();
-                DiagnosticRequestAddress(Id::Extended(response_id))
+                let request_id = ExtendedId::new_masked(raw_offset_id);
+                DiagnosticRequestAddress(Id::Extended(request_id))
             }
         }
     }
+
+    /// Checks if the given request address is the reciprocal of this response address.
+    ///
+    /// Any flags set on either identifier are ignored.
+    ///
+    /// See the documentation of [`DiagnosticResponseAddress`] for more information.
+    pub fn pairs_with(&self, request: &DiagnosticRequestAddress) -> bool {
+        request.pairs_with(self)
+    }
 }
 
-impl Into<Id> for DiagnosticResponseAddress {
-    fn into(self) -> Id {
-        self.0
+impl From<DiagnosticResponseAddress> for Id {
+    fn from(addr: DiagnosticResponseAddress) -> Self {
+        addr.0
     }
 }
 
@@ -247,27 +417,125 @@ impl DiagnosticResponseFilter {
     }
 }
 
-const fn standard_id(id: u16) -> StandardId {
-    match StandardId::new(id) {
-        Some(id) => id,
-        None => panic!("invalid standard ID"),
+/// Filter for physical request addresses for legislated OBD diagnostic messages.
+///
+/// When monitoring the bus, such as when sniffing the traffic between an external test device and
+/// legislated OBD devices, it can be useful to filter out all identifiers that are not used for
+/// physical requests.
+///
+/// This filter only matches identifiers that are valid in the context of being mappable to a
+/// [`DiagnosticRequestAddress`].
+pub struct DiagnosticRequestFilter;
+
+impl DiagnosticRequestFilter {
+    /// Gets the filter for physical request identifiers when using standard addressing.
+    ///
+    /// Standard addressing refers to the 11-bit addressing mode, also known as CAN 2.0A.
+    ///
+    /// Matches identifiers 0x7E0 to 0x7E7, as outlined by ISO 15765-4:2005(E), section
+    /// 6.3.2.2, table 3, "11 bit legislated-OBD CAN identifiers".
+    pub const fn standard() -> Filter {
+        Filter::range(OBD_REQ_ADDR_START_STANDARD, OBD_REQ_ADDR_END_STANDARD)
+    }
+
+    /// Gets the filter for physical request identifiers when using extended addressing.
+    ///
+    /// Extended addressing refers to the 29-bit addressing mode, also known as CAN 2.0B.
+    ///
+    /// Matches identifiers 0x18DA00F1 to 0x18DAFFF1 where the source address is 0xF1, as outlined
+    /// by ISO 15765-4:2005(E), section 6.3.2.3, table 5, "29 bit legislated-OBD CAN identifiers".
+    pub const fn extended() -> Filter {
+        // Only the target address byte varies, so it is left out of the mask, while the source
+        // address must always be 0xF1.
+        Filter::new(
+            OBD_REQ_ADDR_START_EXTENDED,
+            OBD_REQ_TARGET_ADDR_MASK_EXTENDED,
+        )
     }
 }
 
-const fn extended_id(id: u32) -> ExtendedId {
-    match ExtendedId::new(id) {
-        Some(id) => id,
-        None => panic!("invalid extended ID"),
+/// Filter for functional request (broadcast) addresses for legislated OBD diagnostic messages.
+///
+/// This filter only matches the identifier of a [`DiagnosticBroadcastAddress`].
+pub struct DiagnosticBroadcastFilter;
+
+impl DiagnosticBroadcastFilter {
+    /// Gets the filter for the functional request identifier when using standard addressing.
+    ///
+    /// Standard addressing refers to the 11-bit addressing mode, also known as CAN 2.0A.
+    ///
+    /// Matches identifier 0x7DF, as outlined by ISO 15765-4:2005(E), section 6.3.2.2, table 3, "11
+    /// bit legislated-OBD CAN identifiers".
+    pub const fn standard() -> Filter {
+        Filter::from_identity(OBD_BROADCAST_ADDR_STANDARD)
+    }
+
+    /// Gets the filter for the functional request identifier when using extended addressing.
+    ///
+    /// Extended addressing refers to the 29-bit addressing mode, also known as CAN 2.0B.
+    ///
+    /// Matches identifier 0x18DB33F1, as outlined by ISO 15765-4:2005(E), section 6.3.2.3, table
+    /// 5, "29 bit legislated-OBD CAN identifiers".
+    pub const fn extended() -> Filter {
+        Filter::from_identity(OBD_BROADCAST_ADDR_EXTENDED)
     }
 }
 
+/// Creates the response address filter that corresponds to the given request address filter.
+///
+/// When a filter is configured to match physical request addresses, this creates the filter that
+/// will match the reciprocal physical response addresses, such that responses can be captured
+/// without having to separately work out the response addresses.  The identifier of the filter is
+/// mapped from request address to response address in the same way as
+/// [`DiagnosticRequestAddress::into_response_address`], and the mask is kept as-is.  For extended
+/// addressing, where the target and source address bytes are swapped, the same bytes in the mask
+/// are swapped as well.
+///
+/// If the identifier of the given filter is not a valid [`DiagnosticRequestAddress`], `None` is
+/// returned.
+pub fn response_filter_for_requests(request_filter: &Filter) -> Option<Filter> {
+    let request_id = request_filter.id();
+    let response_id = DiagnosticRequestAddress::from_id(request_id)?
+        .into_response_address()
+        .id()
+        .set_flags(request_id.flags());
+
+    let mask = match request_id {
+        Id::Standard(_) => request_filter.mask(),
+        Id::Extended(_) => Mask::new(swap_eid_target_source(request_filter.mask().as_raw())),
+    };
+
+    Some(Filter::new(response_id, mask))
+}
+
 const fn swap_eid_target_source(eid_raw: u32) -> u32 {
     eid_raw & 0xFFFF0000 | (eid_raw & 0x0000FF00) >> 8 | (eid_raw & 0x000000FF) << 8
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::identifier::obd::swap_eid_target_source;
+    use std::vec::Vec;
+
+    use crate::identifier::{
+        id::tests::arb_id,
+        obd::{
+            response_filter_for_requests, swap_eid_target_source, DiagnosticBroadcastAddress,
+            DiagnosticBroadcastFilter, DiagnosticRequestAddress, DiagnosticRequestFilter,
+            DiagnosticResponseAddress,
+        },
+        ExtendedId, Filter, Id, StandardId,
+    };
+
+    use proptest::{proptest, sample::select, strategy::Strategy};
+
+    use super::{OBD_REQ_ADDR_END_STANDARD, OBD_REQ_ADDR_START_STANDARD};
+
+    fn arb_request_address() -> impl Strategy<Value = DiagnosticRequestAddress> {
+        let all = DiagnosticRequestAddress::all_standard()
+            .chain(DiagnosticRequestAddress::all_extended())
+            .collect::<Vec<_>>();
+        select(all)
+    }
 
     #[test]
     fn test_swap_eid_target_source() {
@@ -276,4 +544,225 @@ mod tests {
 
         assert_eq!(expected, swap_eid_target_source(input));
     }
+
+    #[test]
+    fn test_response_filter_for_requests_standard() {
+        let request_filter = Filter::range(OBD_REQ_ADDR_START_STANDARD, OBD_REQ_ADDR_END_STANDARD);
+        let response_filter = response_filter_for_requests(&request_filter).unwrap();
+
+        for raw_id in 0x7E0..=0x7E7 {
+            let id = StandardId::new(raw_id).unwrap().into();
+            assert!(!response_filter.matches(id));
+        }
+
+        for raw_id in 0x7E8..=0x7EF {
+            let id = StandardId::new(raw_id).unwrap().into();
+            assert!(response_filter.matches(id));
+        }
+
+        assert!(!response_filter.matches(StandardId::new(0x7F0).unwrap().into()));
+    }
+
+    #[test]
+    fn test_response_filter_for_requests_extended() {
+        let request_id = ExtendedId::new(0x18DA10F1).unwrap();
+        let request_filter = Filter::from_identity(request_id.into());
+        let response_filter = response_filter_for_requests(&request_filter).unwrap();
+
+        assert!(response_filter.matches(ExtendedId::new(0x18DAF110).unwrap().into()));
+        assert!(!response_filter.matches(ExtendedId::new(0x18DAF111).unwrap().into()));
+        assert!(!response_filter.matches(request_id.into()));
+    }
+
+    #[test]
+    fn test_response_filter_for_requests_invalid() {
+        assert_eq!(None, response_filter_for_requests(&Filter::any()));
+    }
+
+    #[test]
+    fn test_all_standard() {
+        let requests = DiagnosticRequestAddress::all_standard().collect::<Vec<_>>();
+        assert_eq!(8, requests.len());
+        assert_eq!(0x7E0, requests[0].id().as_raw());
+        assert_eq!(0x7E7, requests[7].id().as_raw());
+
+        let responses = DiagnosticResponseAddress::all_standard().collect::<Vec<_>>();
+        assert_eq!(8, responses.len());
+        assert_eq!(0x7E8, responses[0].id().as_raw());
+        assert_eq!(0x7EF, responses[7].id().as_raw());
+
+        for (request, response) in requests.iter().zip(responses.iter()) {
+            assert_eq!(
+                Some(*request),
+                DiagnosticRequestAddress::from_id(request.id())
+            );
+            assert_eq!(*response, request.into_response_address());
+        }
+    }
+
+    #[test]
+    fn test_all_extended() {
+        let requests = DiagnosticRequestAddress::all_extended().collect::<Vec<_>>();
+        assert_eq!(256, requests.len());
+        assert_eq!(0x18DA00F1, requests[0].id().as_raw());
+        assert_eq!(0x18DAFFF1, requests[255].id().as_raw());
+
+        let responses = DiagnosticResponseAddress::all_extended().collect::<Vec<_>>();
+        assert_eq!(256, responses.len());
+        assert_eq!(0x18DAF100, responses[0].id().as_raw());
+        assert_eq!(0x18DAF1FF, responses[255].id().as_raw());
+
+        for (request, response) in requests.iter().zip(responses.iter()) {
+            assert_eq!(
+                Some(*request),
+                DiagnosticRequestAddress::from_id(request.id())
+            );
+            assert_eq!(
+                Some(*response),
+                DiagnosticResponseAddress::from_id(response.id())
+            );
+            assert_eq!(*response, request.into_response_address());
+        }
+    }
+
+    #[test]
+    fn test_from_id_extended_requires_tester_source() {
+        let valid = Id::Extended(ExtendedId::new(0x18DA10F1).unwrap());
+        assert!(DiagnosticRequestAddress::from_id(valid).is_some());
+
+        // Within the request address range, but not sent by the external test equipment.
+        let invalid = Id::Extended(ExtendedId::new(0x18DA1100).unwrap());
+        assert!(DiagnosticRequestAddress::from_id(invalid).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_request_response_round_trip(request in arb_request_address()) {
+            let response = request.into_response_address();
+            assert_eq!(Some(response), DiagnosticResponseAddress::from_id(response.id()));
+            assert_eq!(request, response.into_request_address());
+        }
+
+        #[test]
+        fn test_pairs_with(request in arb_request_address(), other in arb_request_address()) {
+            let response = request.into_response_address();
+            assert!(request.pairs_with(&response));
+            assert!(response.pairs_with(&request));
+
+            let other_response = other.into_response_address();
+            assert_eq!(request == other, request.pairs_with(&other_response));
+            assert_eq!(request == other, other_response.pairs_with(&request));
+        }
+
+        #[test]
+        fn test_from_id_conversions(id in arb_id()) {
+            // Flags are not carried over when converting, so only the identifiers are compared.
+            if let Some(request) = DiagnosticRequestAddress::from_id(id) {
+                let round_tripped = request.into_response_address().into_request_address();
+                assert_eq!(id.as_raw(), round_tripped.id().as_raw());
+            }
+
+            if let Some(response) = DiagnosticResponseAddress::from_id(id) {
+                let round_tripped = response.into_request_address().into_response_address();
+                assert_eq!(id.as_raw(), round_tripped.id().as_raw());
+            }
+        }
+    }
+
+    #[test]
+    fn test_target_source_address() {
+        let request_id = Id::Extended(ExtendedId::new(0x18DA10F1).unwrap());
+        let request = DiagnosticRequestAddress::from_id(request_id).unwrap();
+        assert_eq!(Some(0x10), request.target_address());
+        assert_eq!(Some(0xF1), request.source_address());
+
+        let response_id = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let response = DiagnosticResponseAddress::from_id(response_id).unwrap();
+        assert_eq!(Some(0xF1), response.target_address());
+        assert_eq!(Some(0x10), response.source_address());
+
+        let response_id = Id::Extended(ExtendedId::new(0x18DAF11A).unwrap());
+        let response = DiagnosticResponseAddress::from_id(response_id).unwrap();
+        assert_eq!(Some(0x1A), response.source_address());
+
+        let request_id = Id::Standard(StandardId::new(0x7E0).unwrap());
+        let request = DiagnosticRequestAddress::from_id(request_id).unwrap();
+        assert_eq!(None, request.target_address());
+        assert_eq!(None, request.source_address());
+        assert_eq!(None, request.into_response_address().target_address());
+        assert_eq!(None, request.into_response_address().source_address());
+    }
+
+    fn sid(raw: u16) -> Id {
+        Id::Standard(StandardId::new(raw).unwrap())
+    }
+
+    fn eid(raw: u32) -> Id {
+        Id::Extended(ExtendedId::new(raw).unwrap())
+    }
+
+    #[test]
+    fn test_request_filter() {
+        let filter = DiagnosticRequestFilter::standard();
+        for raw_id in 0x7E0..=0x7E7 {
+            assert!(filter.matches(sid(raw_id)));
+        }
+        assert!(!filter.matches(sid(0x7DF)));
+        assert!(!filter.matches(sid(0x7E8)));
+        assert!(!filter.matches(eid(0x7E0)));
+
+        let filter = DiagnosticRequestFilter::extended();
+        for request in DiagnosticRequestAddress::all_extended() {
+            assert!(filter.matches(request.id()));
+        }
+        assert!(!filter.matches(eid(0x18DA10F0)));
+        assert!(!filter.matches(eid(0x18DA10F2)));
+        assert!(!filter.matches(eid(0x18DB10F1)));
+        assert!(!filter.matches(eid(0x18D910F1)));
+        assert!(!filter.matches(eid(0x18DAF110)));
+    }
+
+    #[test]
+    fn test_broadcast_filter() {
+        let filter = DiagnosticBroadcastFilter::standard();
+        assert!(filter.matches(sid(0x7DF)));
+        assert!(!filter.matches(sid(0x7DE)));
+        assert!(!filter.matches(sid(0x7E0)));
+        assert!(!filter.matches(eid(0x7DF)));
+
+        let filter = DiagnosticBroadcastFilter::extended();
+        assert!(filter.matches(eid(0x18DB33F1)));
+        assert!(!filter.matches(eid(0x18DB33F0)));
+        assert!(!filter.matches(eid(0x18DB33F2)));
+        assert!(!filter.matches(eid(0x18DA33F1)));
+    }
+
+    #[test]
+    fn public_constants() {
+        assert_eq!(
+            DiagnosticBroadcastAddress::standard().id(),
+            DiagnosticBroadcastAddress::STANDARD_ID
+        );
+        assert_eq!(
+            DiagnosticBroadcastAddress::extended().id(),
+            DiagnosticBroadcastAddress::EXTENDED_ID
+        );
+
+        for addr in DiagnosticRequestAddress::all_standard() {
+            assert!(DiagnosticRequestAddress::STANDARD_RANGE.contains(&addr.id()));
+        }
+        for addr in DiagnosticRequestAddress::all_extended() {
+            assert!(DiagnosticRequestAddress::EXTENDED_RANGE.contains(&addr.id()));
+        }
+        for addr in DiagnosticResponseAddress::all_standard() {
+            assert!(DiagnosticResponseAddress::STANDARD_RANGE.contains(&addr.id()));
+        }
+        for addr in DiagnosticResponseAddress::all_extended() {
+            assert!(DiagnosticResponseAddress::EXTENDED_RANGE.contains(&addr.id()));
+        }
+
+        let sid = |raw| Id::Standard(StandardId::new(raw).unwrap());
+        assert!(!DiagnosticRequestAddress::STANDARD_RANGE.contains(&sid(0x7E8)));
+        assert!(!DiagnosticResponseAddress::STANDARD_RANGE.contains(&sid(0x7E7)));
+    }
 }
@@ -0,0 +1,227 @@
+//! Interoperability with [`embedded-can`][::embedded_can].
+//!
+//! The following conversions are implemented:
+//!
+//! - [`StandardId`], [`ExtendedId`], and [`Id`] convert to and from their `embedded-can`
+//!   counterparts with `From`.  As `embedded-can` identifiers have no flags, the remote and error
+//!   flags are dropped when converting to `embedded-can`.
+//! - [`Frame`] implements [`embedded_can::Frame`], so it can be used
+//!   directly with drivers built on `embedded-can`.
+//!
+//! Additionally, [`frame_from_embedded`] and [`frame_to_embedded`] convert between [`Frame`] and
+//! any other type implementing `embedded_can::Frame`, and [`filter_to_id_mask`] exports a
+//! [`Filter`] as the identifier and mask pair expected by most hardware acceptance filters.
+
+#[cfg(feature = "alloc")]
+use bytes::Bytes;
+
+use crate::{
+    constants::EFF_MASK,
+    identifier::{ExtendedId, Filter, Id, StandardId},
+};
+
+#[cfg(feature = "alloc")]
+use crate::frame::Frame;
+
+impl From<StandardId> for ::embedded_can::StandardId {
+    fn from(id: StandardId) -> Self {
+        // SAFETY: `StandardId` is always within the range of a standard identifier.
+        unsafe { ::embedded_can::StandardId::new_unchecked(id.as_raw()) }
+    }
+}
+
+impl From<ExtendedId> for ::embedded_can::ExtendedId {
+    fn from(id: ExtendedId) -> Self {
+        // SAFETY: `ExtendedId` is always within the range of an extended identifier.
+        unsafe { ::embedded_can::ExtendedId::new_unchecked(id.as_raw()) }
+    }
+}
+
+impl From<Id> for ::embedded_can::Id {
+    fn from(id: Id) -> Self {
+        match id {
+            Id::Standard(sid) => ::embedded_can::Id::Standard(sid.into()),
+            Id::Extended(eid) => ::embedded_can::Id::Extended(eid.into()),
+        }
+    }
+}
+
+impl From<::embedded_can::StandardId> for StandardId {
+    fn from(id: ::embedded_can::StandardId) -> Self {
+        StandardId::new_masked(id.as_raw())
+    }
+}
+
+impl From<::embedded_can::ExtendedId> for ExtendedId {
+    fn from(id: ::embedded_can::ExtendedId) -> Self {
+        ExtendedId::new_masked(id.as_raw())
+    }
+}
+
+impl From<::embedded_can::Id> for Id {
+    fn from(id: ::embedded_can::Id) -> Self {
+        match id {
+            ::embedded_can::Id::Standard(sid) => Id::Standard(sid.into()),
+            ::embedded_can::Id::Extended(eid) => Id::Extended(eid.into()),
+        }
+    }
+}
+
+/// Support for using [`Frame`] with [`embedded-can`][::embedded_can].
+///
+/// As `embedded-can` only supports classic CAN frames, frames created through this trait are
+/// limited to eight bytes of data.  Frames with more data than that, such as logical ISO-TP
+/// frames, still expose all of their data via [`data`][::embedded_can::Frame::data], but have a
+/// DLC of eight.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl ::embedded_can::Frame for Frame {
+    fn new(id: impl Into<::embedded_can::Id>, data: &[u8]) -> Result<Self, ()> {
+        let id = Id::from(id.into());
+        Frame::try_new_classic(id, Bytes::copy_from_slice(data)).map_err(|_| ())
+    }
+
+    fn new_remote(id: impl Into<::embedded_can::Id>, dlc: usize) -> Result<Self, ()> {
+        let id = Id::from(id.into());
+        let dlc = u8::try_from(dlc).map_err(|_| ())?;
+        Frame::remote(id, dlc).ok_or(())
+    }
+
+    fn is_extended(&self) -> bool {
+        matches!(self.id(), Id::Extended(_))
+    }
+
+    fn is_remote_frame(&self) -> bool {
+        Frame::is_remote_frame(self)
+    }
+
+    fn is_data_frame(&self) -> bool {
+        Frame::is_data_frame(self)
+    }
+
+    fn id(&self) -> ::embedded_can::Id {
+        Frame::id(self).into()
+    }
+
+    fn dlc(&self) -> usize {
+        usize::from(Frame::dlc(self))
+    }
+
+    fn data(&self) -> &[u8] {
+        Frame::data(self)
+    }
+}
+
+/// Converts an identifier into an `embedded-can` identifier.
+///
+/// This is equivalent to the `From` implementation.  The remote and error flags are dropped.
+pub fn id_to_embedded(id: Id) -> ::embedded_can::Id {
+    id.into()
+}
+
+/// Converts an `embedded-can` identifier into an identifier.
+///
+/// This is equivalent to the `From` implementation.  No flags other than the extended flag, for
+/// extended identifiers, are set.
+pub fn id_from_embedded(id: ::embedded_can::Id) -> Id {
+    id.into()
+}
+
+/// Converts any `embedded-can` frame into a [`Frame`].
+///
+/// Remote frames keep their requested DLC, and data frames copy their data.
+///
+/// # Errors
+///
+/// If the frame is a remote frame with a DLC greater than eight, `None` is returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn frame_from_embedded<F: ::embedded_can::Frame>(frame: &F) -> Option<Frame> {
+    let id = Id::from(frame.id());
+    if frame.is_remote_frame() {
+        Frame::remote(id, u8::try_from(frame.dlc()).ok()?)
+    } else {
+        Some(Frame::from_slice(id, frame.data()))
+    }
+}
+
+/// Converts a [`Frame`] into any `embedded-can` frame.
+///
+/// Remote frames are created with [`new_remote`][::embedded_can::Frame::new_remote], and all other
+/// frames with [`new`][::embedded_can::Frame::new].
+///
+/// # Errors
+///
+/// If the frame type rejects the frame, such as when it has more than eight bytes of data, `None`
+/// is returned.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub fn frame_to_embedded<F: ::embedded_can::Frame>(frame: &Frame) -> Option<F> {
+    let id = ::embedded_can::Id::from(frame.id());
+    match frame.requested_dlc() {
+        Some(dlc) => F::new_remote(id, usize::from(dlc)).ok(),
+        None => F::new(id, frame.data()).ok(),
+    }
+}
+
+/// Exports a [`Filter`] as an `embedded-can` identifier and a raw mask.
+///
+/// Most hardware acceptance filters are configured with an identifier and a mask covering only the
+/// identifier bits, with the addressing mode selected separately.  The addressing mode is carried
+/// by the returned identifier, and the mask is limited to the lower 29 bits, so the remote, error,
+/// and extended flag bits of the filter are dropped.
+pub fn filter_to_id_mask(filter: &Filter) -> (::embedded_can::Id, u32) {
+    (filter.id().into(), filter.mask().as_raw() & EFF_MASK)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::identifier::{ExtendedId, Filter, Id, Mask, StandardId};
+
+    use super::{filter_to_id_mask, id_from_embedded, id_to_embedded};
+
+    #[test]
+    fn ids() {
+        let sid = ::embedded_can::StandardId::new(0x7DF).unwrap();
+        let eid = ::embedded_can::ExtendedId::new(0x18DB33F1).unwrap();
+
+        assert_eq!(
+            ::embedded_can::Id::Standard(sid),
+            id_to_embedded(Id::Standard(StandardId::new(0x7DF).unwrap()))
+        );
+        assert_eq!(
+            Id::Extended(ExtendedId::new(0x18DB33F1).unwrap()),
+            id_from_embedded(::embedded_can::Id::Extended(eid))
+        );
+        assert_eq!(StandardId::new(0x7DF).unwrap(), StandardId::from(sid));
+        assert_eq!(eid, ::embedded_can::ExtendedId::from(ExtendedId::from(eid)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn frames() {
+        use super::{frame_from_embedded, frame_to_embedded};
+        use crate::frame::Frame;
+
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        let round_tripped = frame_from_embedded(&frame_to_embedded::<Frame>(&frame).unwrap());
+        assert_eq!(Some(frame), round_tripped);
+
+        let remote = Frame::remote(id, 4).unwrap();
+        let round_tripped = frame_from_embedded(&frame_to_embedded::<Frame>(&remote).unwrap());
+        assert_eq!(Some(remote), round_tripped);
+
+        let oversized = Frame::from_static(id, &[0; 9]);
+        assert_eq!(None, frame_to_embedded::<Frame>(&oversized));
+    }
+
+    #[test]
+    fn filters() {
+        let id = Id::Extended(ExtendedId::new(0x18DAF100).unwrap());
+        let filter = Filter::new(id, Mask::new(0xFFFF_FF00));
+        let (embedded_id, mask) = filter_to_id_mask(&filter);
+        assert_eq!(::embedded_can::Id::from(id), embedded_id);
+        assert_eq!(0x1FFF_FF00, mask);
+    }
+}
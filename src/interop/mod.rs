@@ -0,0 +1,15 @@
+//! Interoperability with other CAN crates.
+//!
+//! Conversions between the types in this crate and those of other CAN crates are implemented as
+//! standard trait implementations, such as `From` and `TryFrom`, so they can be used anywhere.
+//! Each submodule documents the conversions available for a given crate in one place, and also
+//! provides adapter functions for conversions that are not expressible as trait implementations,
+//! or that are clearer when called by name.
+
+#[cfg(feature = "embedded-can-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-can-compat")))]
+pub mod embedded_can;
+
+#[cfg(feature = "socketcan-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "socketcan-compat")))]
+pub mod socketcan;
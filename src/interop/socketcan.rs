@@ -0,0 +1,361 @@
+//! Interoperability with [`socketcan`].
+//!
+//! The following conversions are implemented:
+//!
+//! - [`Frame`] converts into a [`CanFrame`][::socketcan::CanFrame] with `TryFrom`, failing with
+//!   [`SocketCanFrameError`] when the frame cannot be represented.
+//! - [`CanFrame`][::socketcan::CanFrame] converts into a [`Frame`] with `From`.
+//! - [`Frame`] converts into a [`CanFdFrame`][::socketcan::CanFdFrame] with `TryFrom`, failing with
+//!   [`SocketCanFrameError`] when the frame is not a CAN FD data frame.
+//! - [`CanFdFrame`][::socketcan::CanFdFrame] converts into a [`Frame`] with `From`.
+//! - [`Frame`] converts into a [`CanAnyFrame`][::socketcan::CanAnyFrame] with `TryFrom`, selecting
+//!   the CAN FD or classic frame type based on whether or not the frame is a CAN FD frame.
+//! - [`CanAnyFrame`][::socketcan::CanAnyFrame] converts into a [`Frame`] with `From`.
+//! - [`Filter`] converts into a [`CanFilter`][::socketcan::CanFilter] with `From`.
+//!
+//! The same conversions are available by name as [`frame_to_socketcan`], [`frame_from_socketcan`],
+//! [`frame_to_socketcan_fd`], [`frame_from_socketcan_fd`], [`frame_to_socketcan_any`],
+//! [`frame_from_socketcan_any`], and [`filter_to_socketcan`].
+
+use core::fmt;
+
+use ::socketcan::{EmbeddedFrame as _, Frame as _};
+use bytes::Bytes;
+
+use crate::{
+    frame::{Frame, FrameLengthError, CLASSIC_MAX_DATA_LEN, FD_MAX_DATA_LEN},
+    identifier::{Filter, Id},
+};
+
+/// Error returned when a [`Frame`] cannot be converted into a SocketCAN frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SocketCanFrameError {
+    /// The data of the frame is too large to fit in the SocketCAN frame.
+    TooMuchData(FrameLengthError),
+
+    /// The frame is a CAN FD frame, but a classic CAN frame was required.
+    FdNotSupported,
+
+    /// The frame is not a CAN FD data frame, but a CAN FD frame was required.
+    ///
+    /// Classic CAN frames, remote frames, and error frames cannot be represented as CAN FD frames.
+    NotFd,
+}
+
+impl fmt::Display for SocketCanFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooMuchData(e) => e.fmt(f),
+            Self::FdNotSupported => write!(f, "CAN FD frames are not supported"),
+            Self::NotFd => write!(f, "frame is not a CAN FD data frame"),
+        }
+    }
+}
+
+impl std::error::Error for SocketCanFrameError {}
+
+// SocketCAN marks the addressing mode of an identifier explicitly, so every identifier, including
+// extended identifiers of 0x7FF or less, can be represented.
+fn socketcan_id(id: Id) -> ::socketcan::Id {
+    match id {
+        Id::Standard(sid) => ::socketcan::StandardId::new(sid.as_raw())
+            .expect("standard identifiers should be valid for SocketCAN")
+            .into(),
+        Id::Extended(eid) => ::socketcan::ExtendedId::new(eid.as_raw())
+            .expect("extended identifiers should be valid for SocketCAN")
+            .into(),
+    }
+}
+
+impl TryFrom<Frame> for ::socketcan::CanFrame {
+    type Error = SocketCanFrameError;
+
+    fn try_from(frame: Frame) -> Result<Self, SocketCanFrameError> {
+        if frame.is_fd() {
+            return Err(SocketCanFrameError::FdNotSupported);
+        }
+
+        let too_much_data = |len| {
+            SocketCanFrameError::TooMuchData(FrameLengthError::new(len, CLASSIC_MAX_DATA_LEN))
+        };
+
+        if let Some(dlc) = frame.requested_dlc() {
+            // Remote frames carry no data, so the requested DLC is passed through instead.
+            return ::socketcan::CanRemoteFrame::new_remote(socketcan_id(frame.id()), dlc.into())
+                .map(Self::Remote)
+                .ok_or_else(|| too_much_data(dlc.into()));
+        }
+
+        let data = frame.data();
+        if frame.is_error_frame() {
+            ::socketcan::CanErrorFrame::new_error(frame.id().as_raw(), data)
+                .map(Self::Error)
+                .map_err(|_| too_much_data(data.len()))
+        } else {
+            ::socketcan::CanDataFrame::new(socketcan_id(frame.id()), data)
+                .map(Self::Data)
+                .ok_or_else(|| too_much_data(data.len()))
+        }
+    }
+}
+
+/// Converts a SocketCAN frame into a [`Frame`].
+///
+/// `socketcan` does not expose the time at which a frame was received, so the timestamp of the
+/// frame is not set.  Callers with access to the socket timestamp can set it afterwards with
+/// [`Frame::with_timestamp`].
+impl From<::socketcan::CanFrame> for Frame {
+    fn from(frame: ::socketcan::CanFrame) -> Self {
+        // The identifier word of a SocketCAN frame carries the remote, error, and extended flags.
+        let id = Id::from_raw_with_flags(frame.id_word());
+
+        match frame {
+            // SocketCAN limits the DLC to eight, so the requested DLC is always valid.
+            ::socketcan::CanFrame::Remote(remote) => Frame::remote(id, remote.dlc() as u8)
+                .expect("SocketCAN remote frames should have a valid DLC"),
+            _ => Frame::new(id, Bytes::copy_from_slice(frame.data())),
+        }
+    }
+}
+
+impl TryFrom<Frame> for ::socketcan::CanFdFrame {
+    type Error = SocketCanFrameError;
+
+    fn try_from(frame: Frame) -> Result<Self, SocketCanFrameError> {
+        if !frame.is_fd() || !frame.is_data_frame() {
+            return Err(SocketCanFrameError::NotFd);
+        }
+
+        let mut flags = ::socketcan::id::FdFlags::empty();
+        flags.set(::socketcan::id::FdFlags::BRS, frame.bit_rate_switch());
+        flags.set(::socketcan::id::FdFlags::ESI, frame.error_state_indicator());
+
+        let data = frame.data();
+        ::socketcan::CanFdFrame::with_flags(socketcan_id(frame.id()), data, flags).ok_or(
+            SocketCanFrameError::TooMuchData(FrameLengthError::new(data.len(), FD_MAX_DATA_LEN)),
+        )
+    }
+}
+
+/// Converts a SocketCAN CAN FD frame into a [`Frame`].
+///
+/// The bit rate switch and error state indicator flags are carried over.  As with classic frames,
+/// the timestamp of the frame is not set.
+impl From<::socketcan::CanFdFrame> for Frame {
+    fn from(frame: ::socketcan::CanFdFrame) -> Self {
+        let id = Id::from_raw_with_flags(frame.id_word());
+
+        // SocketCAN pads CAN FD data to a valid length of at most 64 bytes.
+        Frame::new_fd(id, Bytes::copy_from_slice(frame.data()), frame.is_brs())
+            .expect("SocketCAN CAN FD frames should have a valid data length")
+            .with_error_state_indicator(frame.is_esi())
+            .expect("CAN FD frames should accept the error state indicator")
+    }
+}
+
+impl TryFrom<Frame> for ::socketcan::CanAnyFrame {
+    type Error = SocketCanFrameError;
+
+    fn try_from(frame: Frame) -> Result<Self, SocketCanFrameError> {
+        if frame.is_fd() {
+            ::socketcan::CanFdFrame::try_from(frame).map(Self::Fd)
+        } else {
+            ::socketcan::CanFrame::try_from(frame).map(Self::from)
+        }
+    }
+}
+
+/// Converts any SocketCAN frame into a [`Frame`].
+///
+/// CAN FD frames become CAN FD frames, and all other frames become classic CAN frames.  As with
+/// the other conversions, the timestamp of the frame is not set.
+impl From<::socketcan::CanAnyFrame> for Frame {
+    fn from(frame: ::socketcan::CanAnyFrame) -> Self {
+        match frame {
+            ::socketcan::CanAnyFrame::Normal(frame) => ::socketcan::CanFrame::Data(frame).into(),
+            ::socketcan::CanAnyFrame::Remote(frame) => ::socketcan::CanFrame::Remote(frame).into(),
+            ::socketcan::CanAnyFrame::Error(frame) => ::socketcan::CanFrame::Error(frame).into(),
+            ::socketcan::CanAnyFrame::Fd(frame) => frame.into(),
+        }
+    }
+}
+
+impl From<Filter> for ::socketcan::CanFilter {
+    fn from(filter: Filter) -> Self {
+        let (id, mask) = filter.to_raw();
+        ::socketcan::CanFilter::new(id, mask)
+    }
+}
+
+/// Converts a [`Frame`] into a SocketCAN frame.
+///
+/// This is equivalent to the `TryFrom` implementation.
+///
+/// # Errors
+///
+/// If the frame is a CAN FD frame, or has more than eight bytes of data, an error is returned.
+pub fn frame_to_socketcan(frame: Frame) -> Result<::socketcan::CanFrame, SocketCanFrameError> {
+    ::socketcan::CanFrame::try_from(frame)
+}
+
+/// Converts a SocketCAN frame into a [`Frame`].
+///
+/// This is equivalent to the `From` implementation, and likewise does not set the timestamp of the
+/// frame.
+pub fn frame_from_socketcan(frame: ::socketcan::CanFrame) -> Frame {
+    frame.into()
+}
+
+/// Converts a [`Frame`] into a SocketCAN CAN FD frame.
+///
+/// This is equivalent to the `TryFrom` implementation.  SocketCAN pads the data to the next valid
+/// CAN FD data length.
+///
+/// # Errors
+///
+/// If the frame is not a CAN FD data frame, or has more than 64 bytes of data, an error is returned.
+pub fn frame_to_socketcan_fd(frame: Frame) -> Result<::socketcan::CanFdFrame, SocketCanFrameError> {
+    ::socketcan::CanFdFrame::try_from(frame)
+}
+
+/// Converts a SocketCAN CAN FD frame into a [`Frame`].
+///
+/// This is equivalent to the `From` implementation, and likewise does not set the timestamp of the
+/// frame.
+pub fn frame_from_socketcan_fd(frame: ::socketcan::CanFdFrame) -> Frame {
+    frame.into()
+}
+
+/// Converts a [`Frame`] into either a classic or CAN FD SocketCAN frame.
+///
+/// This is equivalent to the `TryFrom` implementation.
+///
+/// # Errors
+///
+/// If the frame has more data than its frame type allows, an error is returned.
+pub fn frame_to_socketcan_any(
+    frame: Frame,
+) -> Result<::socketcan::CanAnyFrame, SocketCanFrameError> {
+    ::socketcan::CanAnyFrame::try_from(frame)
+}
+
+/// Converts any SocketCAN frame into a [`Frame`].
+///
+/// This is equivalent to the `From` implementation, and likewise does not set the timestamp of the
+/// frame.
+pub fn frame_from_socketcan_any(frame: ::socketcan::CanAnyFrame) -> Frame {
+    frame.into()
+}
+
+/// Converts a [`Filter`] into a SocketCAN filter.
+///
+/// This is equivalent to the `From` implementation.  The raw identifier and mask, including the
+/// remote, error, and extended flag bits, are passed through as-is.
+pub fn filter_to_socketcan(filter: Filter) -> ::socketcan::CanFilter {
+    filter.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{
+        constants::FdFlags,
+        frame::{self, Frame},
+        identifier::{ExtendedId, Filter, Id, StandardId},
+    };
+
+    use super::{
+        filter_to_socketcan, frame_from_socketcan, frame_from_socketcan_any,
+        frame_from_socketcan_fd, frame_to_socketcan, frame_to_socketcan_any, frame_to_socketcan_fd,
+        SocketCanFrameError,
+    };
+
+    #[test]
+    fn frames() {
+        let id = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        let socketcan_frame = frame_to_socketcan(frame.clone()).unwrap();
+        assert_eq!(frame, frame_from_socketcan(socketcan_frame));
+
+        let id = Id::Extended(ExtendedId::new(0x123).unwrap());
+        let frame = Frame::from_static(id, &[]);
+        let socketcan_frame = frame_to_socketcan(frame.clone()).unwrap();
+        assert_eq!(frame, frame_from_socketcan(socketcan_frame));
+
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::remote(id, 3).unwrap();
+        let socketcan_frame = frame_to_socketcan(frame.clone()).unwrap();
+        assert_eq!(frame, frame_from_socketcan(socketcan_frame));
+    }
+
+    #[test]
+    fn fd_frames() {
+        let id = Id::Extended(ExtendedId::new(0x18DAF110).unwrap());
+        let data: Bytes = (0..64).collect();
+        let frame = Frame::new_fd(id, data, true)
+            .unwrap()
+            .with_error_state_indicator(true)
+            .unwrap();
+        assert_eq!(FdFlags::all(), frame.fd_flags());
+
+        let socketcan_frame = frame_to_socketcan_fd(frame.clone()).unwrap();
+        assert!(socketcan_frame.is_brs());
+        assert!(socketcan_frame.is_esi());
+        assert_eq!(
+            frame.data(),
+            socketcan::EmbeddedFrame::data(&socketcan_frame)
+        );
+        assert_eq!(frame, frame_from_socketcan_fd(socketcan_frame));
+
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+        let frame = Frame::new_fd(id, Bytes::from_static(&[0x02, 0x01, 0x0C]), false).unwrap();
+        let socketcan_frame = frame_to_socketcan_fd(frame.clone()).unwrap();
+        assert!(!socketcan_frame.is_brs());
+        assert!(!socketcan_frame.is_esi());
+        assert_eq!(frame, frame_from_socketcan_fd(socketcan_frame));
+
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        assert_eq!(
+            Err(SocketCanFrameError::NotFd),
+            frame_to_socketcan_fd(frame).map(|_| ())
+        );
+
+        let frame = Frame::remote(id, 3).unwrap();
+        assert_eq!(
+            Err(SocketCanFrameError::NotFd),
+            frame_to_socketcan_fd(frame).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn any_frames() {
+        let id = Id::Standard(StandardId::new(0x7DF).unwrap());
+
+        let frame = Frame::from_static(id, &[0x02, 0x01, 0x0C]);
+        let socketcan_frame = frame_to_socketcan_any(frame.clone()).unwrap();
+        assert!(matches!(socketcan_frame, socketcan::CanAnyFrame::Normal(_)));
+        assert_eq!(frame, frame_from_socketcan_any(socketcan_frame));
+
+        let frame = Frame::new_fd(id, (0..64).collect(), true).unwrap();
+        let socketcan_frame = frame_to_socketcan_any(frame.clone()).unwrap();
+        assert!(matches!(socketcan_frame, socketcan::CanAnyFrame::Fd(_)));
+        assert_eq!(frame, frame_from_socketcan_any(socketcan_frame));
+    }
+
+    #[test]
+    fn error_is_re_exported() {
+        let id = Id::Standard(StandardId::new(0x123).unwrap());
+        let frame = Frame::new_fd(id, Bytes::from_static(&[]), false).unwrap();
+        let err: frame::SocketCanFrameError = frame_to_socketcan(frame).unwrap_err();
+        assert_eq!(SocketCanFrameError::FdNotSupported, err);
+    }
+
+    #[test]
+    fn filters() {
+        let filter = Filter::from_raw(0x18DAF100, 0x1FFFFF00);
+        let (raw_id, raw_mask) = filter.to_raw();
+        let expected = ::socketcan::CanFilter::new(raw_id, raw_mask);
+        assert_eq!(expected, filter_to_socketcan(filter));
+    }
+}
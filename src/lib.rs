@@ -8,16 +8,46 @@
 //! While the types are ostensibly meant to be foundational and thus shared amongst the ecosystem,
 //! the crate does provide conversion implementations for popular CAN-related crates to allow for interoperation:
 //!
-//! - **embedded-can-compat**: supports converting identifiers into [`embedded-can`][embedded-can] identifiers
-//! - **socketcan-compat**: supports converting filters into [socketcan][socketcan] filters
+//! - **std**: enables functionality that depends on the standard library, such as implementing
+//!   `std::error::Error` for error types (implies **alloc**)
+//! - **alloc**: enables functionality that depends on an allocator, such as [`Frame`][frame::Frame]
+//! - **embedded-can-compat**: supports converting identifiers to and from [`embedded-can`][embedded-can] identifiers, and
+//!   using frames as `embedded-can` frames
+//! - **socketcan-compat**: supports converting filters and frames into [socketcan][socketcan] filters and frames
+//! - **defmt**: implements [`defmt::Format`][defmt] for identifiers, flags, masks, filters, and
+//!   frames, for logging on embedded targets
 //!
-//! All feature flags are enabled by default.
+//! All feature flags, other than **defmt**, are enabled by default.
 //!
+//! ## `no_std` support
+//!
+//! When the **std** feature is disabled, the crate is `no_std`, and the identifier, filter, and
+//! flag types remain available.  Frames additionally require the **alloc** feature.  The
+//! **socketcan-compat** feature depends on the standard library, and so implies **std**.
+//!
+//! [defmt]: https://docs.rs/defmt/latest/defmt/trait.Format.html
 //! [embedded-can]: https://docs.rs/embedded-can/latest/embedded_can/
 //! [socketcan]: https://docs.rs/socketcan/latest/socketcan/
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg), deny(rustdoc::broken_intra_doc_links))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(test)]
+#[macro_use]
+extern crate std;
+
 pub mod constants;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub mod frame;
 pub mod identifier;
+#[cfg(any(feature = "embedded-can-compat", feature = "socketcan-compat"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "embedded-can-compat", feature = "socketcan-compat")))
+)]
+pub mod interop;
+pub mod uds;
@@ -0,0 +1,297 @@
+//! UDS-specific (Unified Diagnostic Services) types, based on ISO 14229-1.
+//!
+//! UDS is typically carried over ISO-TP, where the first byte of each payload is the service
+//! identifier of a request, or the response identifier of a response.  Positive responses use the
+//! service identifier of the request plus 0x40, while negative responses use a dedicated response
+//! identifier, 0x7F, followed by the service identifier of the request and a negative response
+//! code.
+
+use core::fmt;
+
+const POSITIVE_RESPONSE_OFFSET: u8 = 0x40;
+
+/// Response identifier for negative responses.
+pub const NEGATIVE_RESPONSE_SID: u8 = 0x7F;
+
+/// Service identifier of a UDS request.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum ServiceId {
+    /// Diagnostic Session Control (0x10).
+    DiagnosticSessionControl = 0x10,
+
+    /// ECU Reset (0x11).
+    EcuReset = 0x11,
+
+    /// Clear Diagnostic Information (0x14).
+    ClearDiagnosticInformation = 0x14,
+
+    /// Read DTC Information (0x19).
+    ReadDtcInformation = 0x19,
+
+    /// Read Data By Identifier (0x22).
+    ReadDataByIdentifier = 0x22,
+
+    /// Read Memory By Address (0x23).
+    ReadMemoryByAddress = 0x23,
+
+    /// Read Scaling Data By Identifier (0x24).
+    ReadScalingDataByIdentifier = 0x24,
+
+    /// Security Access (0x27).
+    SecurityAccess = 0x27,
+
+    /// Communication Control (0x28).
+    CommunicationControl = 0x28,
+
+    /// Authentication (0x29).
+    Authentication = 0x29,
+
+    /// Read Data By Periodic Identifier (0x2A).
+    ReadDataByPeriodicIdentifier = 0x2A,
+
+    /// Dynamically Define Data Identifier (0x2C).
+    DynamicallyDefineDataIdentifier = 0x2C,
+
+    /// Write Data By Identifier (0x2E).
+    WriteDataByIdentifier = 0x2E,
+
+    /// Input Output Control By Identifier (0x2F).
+    InputOutputControlByIdentifier = 0x2F,
+
+    /// Routine Control (0x31).
+    RoutineControl = 0x31,
+
+    /// Request Download (0x34).
+    RequestDownload = 0x34,
+
+    /// Request Upload (0x35).
+    RequestUpload = 0x35,
+
+    /// Transfer Data (0x36).
+    TransferData = 0x36,
+
+    /// Request Transfer Exit (0x37).
+    RequestTransferExit = 0x37,
+
+    /// Request File Transfer (0x38).
+    RequestFileTransfer = 0x38,
+
+    /// Write Memory By Address (0x3D).
+    WriteMemoryByAddress = 0x3D,
+
+    /// Tester Present (0x3E).
+    TesterPresent = 0x3E,
+
+    /// Access Timing Parameter (0x83).
+    AccessTimingParameter = 0x83,
+
+    /// Secured Data Transmission (0x84).
+    SecuredDataTransmission = 0x84,
+
+    /// Control DTC Setting (0x85).
+    ControlDtcSetting = 0x85,
+
+    /// Response On Event (0x86).
+    ResponseOnEvent = 0x86,
+
+    /// Link Control (0x87).
+    LinkControl = 0x87,
+}
+
+impl ServiceId {
+    /// Creates a `ServiceId` from its raw value.
+    ///
+    /// Returns `None` if `raw` is not a known service identifier.
+    pub const fn from_raw(raw: u8) -> Option<Self> {
+        let sid = match raw {
+            0x10 => Self::DiagnosticSessionControl,
+            0x11 => Self::EcuReset,
+            0x14 => Self::ClearDiagnosticInformation,
+            0x19 => Self::ReadDtcInformation,
+            0x22 => Self::ReadDataByIdentifier,
+            0x23 => Self::ReadMemoryByAddress,
+            0x24 => Self::ReadScalingDataByIdentifier,
+            0x27 => Self::SecurityAccess,
+            0x28 => Self::CommunicationControl,
+            0x29 => Self::Authentication,
+            0x2A => Self::ReadDataByPeriodicIdentifier,
+            0x2C => Self::DynamicallyDefineDataIdentifier,
+            0x2E => Self::WriteDataByIdentifier,
+            0x2F => Self::InputOutputControlByIdentifier,
+            0x31 => Self::RoutineControl,
+            0x34 => Self::RequestDownload,
+            0x35 => Self::RequestUpload,
+            0x36 => Self::TransferData,
+            0x37 => Self::RequestTransferExit,
+            0x38 => Self::RequestFileTransfer,
+            0x3D => Self::WriteMemoryByAddress,
+            0x3E => Self::TesterPresent,
+            0x83 => Self::AccessTimingParameter,
+            0x84 => Self::SecuredDataTransmission,
+            0x85 => Self::ControlDtcSetting,
+            0x86 => Self::ResponseOnEvent,
+            0x87 => Self::LinkControl,
+            _ => return None,
+        };
+
+        Some(sid)
+    }
+
+    /// Returns the service identifier as a raw integer.
+    pub const fn as_raw(self) -> u8 {
+        self as u8
+    }
+
+    /// Gets the response identifier of a positive response to this service.
+    ///
+    /// Positive responses use the service identifier of the request plus 0x40.
+    pub const fn positive_response(self) -> u8 {
+        self.as_raw() + POSITIVE_RESPONSE_OFFSET
+    }
+}
+
+impl From<ServiceId> for u8 {
+    fn from(sid: ServiceId) -> Self {
+        sid.as_raw()
+    }
+}
+
+/// Negative response to a UDS request.
+///
+/// Wraps the negative response code (NRC) that describes why the request was rejected.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NegativeResponse(u8);
+
+impl NegativeResponse {
+    /// Creates a `NegativeResponse` from a negative response code.
+    pub const fn new(code: u8) -> Self {
+        Self(code)
+    }
+
+    /// Parses a negative response from the payload of a response.
+    ///
+    /// A negative response payload consists of the negative response identifier, 0x7F, followed by
+    /// the service identifier of the request, and then the negative response code.  The raw
+    /// service identifier of the request is returned alongside the negative response.
+    ///
+    /// # Errors
+    ///
+    /// If the payload is not a negative response, `None` is returned.
+    pub fn parse(payload: &[u8]) -> Option<(u8, Self)> {
+        match payload {
+            [NEGATIVE_RESPONSE_SID, sid, code, ..] => Some((*sid, Self(*code))),
+            _ => None,
+        }
+    }
+
+    /// Gets the negative response code.
+    pub const fn code(&self) -> u8 {
+        self.0
+    }
+
+    /// Gets the human-readable name of the negative response code.
+    ///
+    /// Returns `None` if the code is not a known negative response code.
+    pub const fn name(&self) -> Option<&'static str> {
+        let name = match self.0 {
+            0x10 => "general reject",
+            0x11 => "service not supported",
+            0x12 => "sub-function not supported",
+            0x13 => "incorrect message length or invalid format",
+            0x14 => "response too long",
+            0x21 => "busy, repeat request",
+            0x22 => "conditions not correct",
+            0x24 => "request sequence error",
+            0x25 => "no response from subnet component",
+            0x26 => "failure prevents execution of requested action",
+            0x31 => "request out of range",
+            0x33 => "security access denied",
+            0x35 => "invalid key",
+            0x36 => "exceeded number of attempts",
+            0x37 => "required time delay not expired",
+            0x70 => "upload/download not accepted",
+            0x71 => "transfer data suspended",
+            0x72 => "general programming failure",
+            0x73 => "wrong block sequence counter",
+            0x78 => "request correctly received, response pending",
+            0x7E => "sub-function not supported in active session",
+            0x7F => "service not supported in active session",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+
+    /// Whether or not this indicates that the response is pending.
+    ///
+    /// A pending response is not a failure: the server has received the request, but needs more
+    /// time before sending the actual response.
+    pub const fn is_response_pending(&self) -> bool {
+        self.0 == 0x78
+    }
+}
+
+impl fmt::Display for NegativeResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{} ({:#04X})", name, self.0),
+            None => write!(f, "unknown negative response code ({:#04X})", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use super::{NegativeResponse, ServiceId};
+
+    #[test]
+    fn positive_response() {
+        assert_eq!(
+            0x50,
+            ServiceId::DiagnosticSessionControl.positive_response()
+        );
+        assert_eq!(0x62, ServiceId::ReadDataByIdentifier.positive_response());
+        assert_eq!(0x67, ServiceId::SecurityAccess.positive_response());
+        assert_eq!(0x7E, ServiceId::TesterPresent.positive_response());
+        assert_eq!(0xC5, ServiceId::ControlDtcSetting.positive_response());
+    }
+
+    #[test]
+    fn service_id_round_trip() {
+        for raw in 0..=u8::MAX {
+            if let Some(sid) = ServiceId::from_raw(raw) {
+                assert_eq!(raw, sid.as_raw());
+            }
+        }
+
+        assert_eq!(Some(ServiceId::RoutineControl), ServiceId::from_raw(0x31));
+        assert_eq!(None, ServiceId::from_raw(0x7F));
+    }
+
+    #[test]
+    fn negative_response() {
+        let nrc = NegativeResponse::new(0x31);
+        assert_eq!(Some("request out of range"), nrc.name());
+        assert_eq!("request out of range (0x31)", nrc.to_string());
+
+        let nrc = NegativeResponse::new(0x33);
+        assert_eq!("security access denied (0x33)", nrc.to_string());
+
+        let nrc = NegativeResponse::new(0x78);
+        assert!(nrc.is_response_pending());
+
+        let nrc = NegativeResponse::new(0x99);
+        assert_eq!(None, nrc.name());
+        assert_eq!("unknown negative response code (0x99)", nrc.to_string());
+
+        assert_eq!(
+            Some((0x22, NegativeResponse::new(0x31))),
+            NegativeResponse::parse(&[0x7F, 0x22, 0x31])
+        );
+        assert_eq!(None, NegativeResponse::parse(&[0x62, 0xF1, 0x90]));
+        assert_eq!(None, NegativeResponse::parse(&[0x7F, 0x22]));
+    }
+}